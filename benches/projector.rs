@@ -0,0 +1,87 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use kma_grid::{GridSpec, KmaGrid, Projector};
+
+// points spread over the DFS domain
+fn points() -> Vec<(f64, f64)> {
+    (0..1000)
+        .map(|i| {
+            let t = i as f64 / 1000.0;
+            (124.0 + 6.0 * t, 33.0 + 6.0 * (1.0 - t))
+        })
+        .collect()
+}
+
+fn from_gcs(c: &mut Criterion) {
+    let points = points();
+    let spec = GridSpec::DFS_5KM;
+    let projector = Projector::new(spec);
+
+    let mut group = c.benchmark_group("from_gcs");
+    group.bench_function("spec", |b| {
+        b.iter(|| {
+            for &(longitude, latitude) in &points {
+                black_box(
+                    spec.gcs_to_grid(black_box(longitude), black_box(latitude))
+                        .unwrap(),
+                );
+            }
+        })
+    });
+    group.bench_function("projector", |b| {
+        b.iter(|| {
+            for &(longitude, latitude) in &points {
+                black_box(
+                    projector
+                        .gcs_to_grid(black_box(longitude), black_box(latitude))
+                        .unwrap(),
+                );
+            }
+        })
+    });
+    group.bench_function("kma_grid", |b| {
+        b.iter(|| {
+            for &(longitude, latitude) in &points {
+                black_box(KmaGrid::from_gcs(black_box(longitude), black_box(latitude)));
+            }
+        })
+    });
+    group.bench_function("slice", |b| {
+        let (longitudes, latitudes): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+        let (mut xs, mut ys, mut valid) = (vec![0; 1000], vec![0; 1000], vec![false; 1000]);
+        b.iter(|| {
+            black_box(KmaGrid::from_gcs_slice(
+                black_box(&longitudes),
+                black_box(&latitudes),
+                &mut xs,
+                &mut ys,
+                &mut valid,
+            ))
+        })
+    });
+    group.finish();
+}
+
+fn to_gcs(c: &mut Criterion) {
+    let spec = GridSpec::DFS_5KM;
+    let projector = Projector::new(spec);
+
+    let mut group = c.benchmark_group("to_gcs");
+    group.bench_function("spec", |b| {
+        b.iter(|| {
+            for y in 1..=spec.ny {
+                black_box(spec.grid_to_gcs(black_box(75.0), black_box(y as f64)));
+            }
+        })
+    });
+    group.bench_function("projector", |b| {
+        b.iter(|| {
+            for y in 1..=spec.ny {
+                black_box(projector.grid_to_gcs(black_box(75.0), black_box(y as f64)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, from_gcs, to_gcs);
+criterion_main!(benches);
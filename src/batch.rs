@@ -0,0 +1,367 @@
+use crate::kma_grid::{KmaGrid, OutOfRange};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::Projection;
+use crate::projector::Projector;
+
+// Conversions over columns of coordinates. All slices of a call must be of the
+// same length. An element that fails to convert is marked false in valid and
+// gets 0 for a grid and NaN for a coordinate, without stopping the others.
+// Each returns the number of valid elements.
+
+pub(crate) fn check_lengths(lengths: &[usize]) {
+    assert!(
+        lengths.iter().all(|&length| length == lengths[0]),
+        "slices of different lengths {:?}",
+        lengths
+    );
+}
+
+// Points go through in chunks of CHUNK: first all are projected with NaN for
+// those that do not project, then a second pass masks and rounds them, so that
+// neither loop branches on a Result.
+const CHUNK: usize = 256;
+
+impl<P: Projection> ProjectedGrid<P> {
+    // gcs_to_grid without the checks, NaN where it would fail
+    fn project_slice(&self, longitudes: &[f64], latitudes: &[f64], xs: &mut [f64], ys: &mut [f64]) {
+        for (((&longitude, &latitude), x), y) in longitudes
+            .iter()
+            .zip(latitudes)
+            .zip(xs.iter_mut())
+            .zip(ys.iter_mut())
+        {
+            let (plane_x, plane_y) = self.projection.forward_or_nan(longitude, latitude);
+            *x = plane_x / self.dx + self.reference_x;
+            *y = plane_y / self.dy + self.reference_y;
+        }
+    }
+
+    pub fn from_gcs_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        out_of_range: OutOfRange,
+        xs: &mut [i32],
+        ys: &mut [i32],
+        valid: &mut [bool],
+    ) -> usize {
+        check_lengths(&[
+            longitudes.len(),
+            latitudes.len(),
+            xs.len(),
+            ys.len(),
+            valid.len(),
+        ]);
+        let (mut grid_xs, mut grid_ys) = ([0.0; CHUNK], [0.0; CHUNK]);
+        let mut count = 0;
+        for ((((longitudes, latitudes), xs), ys), valid) in longitudes
+            .chunks(CHUNK)
+            .zip(latitudes.chunks(CHUNK))
+            .zip(xs.chunks_mut(CHUNK))
+            .zip(ys.chunks_mut(CHUNK))
+            .zip(valid.chunks_mut(CHUNK))
+        {
+            let (grid_xs, grid_ys) = (&mut grid_xs[..xs.len()], &mut grid_ys[..xs.len()]);
+            self.project_slice(longitudes, latitudes, grid_xs, grid_ys);
+            for ((((&grid_x, &grid_y), x), y), valid) in grid_xs
+                .iter()
+                .zip(grid_ys.iter())
+                .zip(xs.iter_mut())
+                .zip(ys.iter_mut())
+                .zip(valid.iter_mut())
+            {
+                let projected = grid_x.is_finite() & grid_y.is_finite();
+                let (grid_x, grid_y) = (grid_x.round() as i32, grid_y.round() as i32);
+                let inside = (1..=self.nx).contains(&grid_x) & (1..=self.ny).contains(&grid_y);
+                let (grid_x, grid_y, kept) = match out_of_range {
+                    OutOfRange::Error => (grid_x, grid_y, inside),
+                    OutOfRange::Clamp => (grid_x.clamp(1, self.nx), grid_y.clamp(1, self.ny), true),
+                    OutOfRange::Unbounded => (grid_x, grid_y, true),
+                };
+                *valid = projected & kept;
+                (*x, *y) = if *valid { (grid_x, grid_y) } else { (0, 0) };
+                count += *valid as usize;
+            }
+        }
+        count
+    }
+
+    pub fn to_gcs_slice(
+        &self,
+        xs: &[i32],
+        ys: &[i32],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        check_lengths(&[
+            xs.len(),
+            ys.len(),
+            longitudes.len(),
+            latitudes.len(),
+            valid.len(),
+        ]);
+        // every grid has coordinates, those outside the domain are masked after
+        for (((&x, &y), longitude), latitude) in xs
+            .iter()
+            .zip(ys)
+            .zip(longitudes.iter_mut())
+            .zip(latitudes.iter_mut())
+        {
+            (*longitude, *latitude) = self.grid_to_gcs(x as f64, y as f64);
+        }
+        let mut count = 0;
+        for ((((&x, &y), longitude), latitude), valid) in xs
+            .iter()
+            .zip(ys)
+            .zip(longitudes.iter_mut())
+            .zip(latitudes.iter_mut())
+            .zip(valid.iter_mut())
+        {
+            *valid = (1..=self.nx).contains(&x) & (1..=self.ny).contains(&y);
+            if !*valid {
+                (*longitude, *latitude) = (f64::NAN, f64::NAN);
+            }
+            count += *valid as usize;
+        }
+        count
+    }
+
+    // unrounded grid coordinates
+    pub fn gcs_to_grid_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        xs: &mut [f64],
+        ys: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        check_lengths(&[
+            longitudes.len(),
+            latitudes.len(),
+            xs.len(),
+            ys.len(),
+            valid.len(),
+        ]);
+        self.project_slice(longitudes, latitudes, xs, ys);
+        let mut count = 0;
+        for ((x, y), valid) in xs.iter_mut().zip(ys.iter_mut()).zip(valid.iter_mut()) {
+            *valid = x.is_finite() & y.is_finite();
+            if !*valid {
+                (*x, *y) = (f64::NAN, f64::NAN);
+            }
+            count += *valid as usize;
+        }
+        count
+    }
+
+    // every element converts, so there is no mask
+    pub fn grid_to_gcs_slice(
+        &self,
+        xs: &[f64],
+        ys: &[f64],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+    ) {
+        check_lengths(&[xs.len(), ys.len(), longitudes.len(), latitudes.len()]);
+        for (((&x, &y), longitude), latitude) in xs
+            .iter()
+            .zip(ys)
+            .zip(longitudes.iter_mut())
+            .zip(latitudes.iter_mut())
+        {
+            (*longitude, *latitude) = self.grid_to_gcs(x, y);
+        }
+    }
+}
+
+impl Projector {
+    pub fn from_gcs_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        out_of_range: OutOfRange,
+        xs: &mut [i32],
+        ys: &mut [i32],
+        valid: &mut [bool],
+    ) -> usize {
+        self.projected_grid()
+            .from_gcs_slice(longitudes, latitudes, out_of_range, xs, ys, valid)
+    }
+
+    pub fn to_gcs_slice(
+        &self,
+        xs: &[i32],
+        ys: &[i32],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        self.projected_grid()
+            .to_gcs_slice(xs, ys, longitudes, latitudes, valid)
+    }
+
+    pub fn gcs_to_grid_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        xs: &mut [f64],
+        ys: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        self.projected_grid()
+            .gcs_to_grid_slice(longitudes, latitudes, xs, ys, valid)
+    }
+
+    pub fn grid_to_gcs_slice(
+        &self,
+        xs: &[f64],
+        ys: &[f64],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+    ) {
+        self.projected_grid()
+            .grid_to_gcs_slice(xs, ys, longitudes, latitudes)
+    }
+}
+
+impl KmaGrid {
+    // try_from_gcs over slices on GridSpec::DFS_5KM
+    pub fn from_gcs_slice(
+        longitudes: &[f64],
+        latitudes: &[f64],
+        xs: &mut [i32],
+        ys: &mut [i32],
+        valid: &mut [bool],
+    ) -> usize {
+        Projector::dfs().from_gcs_slice(longitudes, latitudes, OutOfRange::Error, xs, ys, valid)
+    }
+
+    // to_gcs over slices on GridSpec::DFS_5KM
+    pub fn to_gcs_slice(
+        xs: &[i32],
+        ys: &[i32],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        Projector::dfs().to_gcs_slice(xs, ys, longitudes, latitudes, valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_as_single() {
+        let longitudes = [126.0, 126.978, f64::NAN, 139.69, 131.8647, 127.0];
+        let latitudes = [38.0, 37.5665, 37.0, 35.69, 37.2426, 95.0];
+        let n = longitudes.len();
+
+        let (mut xs, mut ys, mut valid) = (vec![-1; n], vec![-1; n], vec![true; n]);
+        let count = KmaGrid::from_gcs_slice(&longitudes, &latitudes, &mut xs, &mut ys, &mut valid);
+        assert_eq!(count, 3);
+        assert_eq!(valid, [true, true, false, false, true, false]);
+        for i in 0..n {
+            match KmaGrid::try_from_gcs(longitudes[i], latitudes[i]) {
+                Ok(grid) => assert_eq!((xs[i], ys[i]), (grid.x(), grid.y())),
+                Err(_) => assert_eq!((xs[i], ys[i]), (0, 0)),
+            }
+        }
+
+        let (mut lons, mut lats) = (vec![0.0; n], vec![0.0; n]);
+        let count = KmaGrid::to_gcs_slice(&xs, &ys, &mut lons, &mut lats, &mut valid);
+        assert_eq!(count, 3);
+        assert_eq!(KmaGrid::new(xs[1], ys[1]).to_gcs(), Ok((lons[1], lats[1])));
+        assert!(lons[2].is_nan() && lats[2].is_nan());
+    }
+
+    #[test]
+    fn same_as_single_over_chunks() {
+        // more points than a chunk, with the ones that do not project among them
+        let mut points: Vec<(f64, f64)> = (0..700)
+            .map(|i| (110.0 + 0.04 * i as f64, 20.0 + 0.035 * i as f64))
+            .collect();
+        points.extend([
+            (f64::NAN, 38.0),
+            (126.0, f64::NAN),
+            (f64::INFINITY, 38.0),
+            (126.0, f64::NEG_INFINITY),
+            (126.0, 90.0),
+            (126.0, -90.0),
+            (126.0, 90.5),
+            (1e300, 38.0),
+            (126.0 + 720.0, 38.0),
+        ]);
+        let (longitudes, latitudes): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+        let n = points.len();
+        let projector = Projector::dfs();
+
+        for out_of_range in [OutOfRange::Error, OutOfRange::Clamp, OutOfRange::Unbounded] {
+            let (mut xs, mut ys, mut valid) = (vec![-1; n], vec![-1; n], vec![false; n]);
+            let count = projector.from_gcs_slice(
+                &longitudes,
+                &latitudes,
+                out_of_range,
+                &mut xs,
+                &mut ys,
+                &mut valid,
+            );
+            let mut expected = 0;
+            for i in 0..n {
+                match projector.from_gcs_with(longitudes[i], latitudes[i], out_of_range) {
+                    Ok(grid) => {
+                        assert_eq!((xs[i], ys[i], valid[i]), (grid.x(), grid.y(), true));
+                        expected += 1;
+                    }
+                    Err(_) => assert_eq!((xs[i], ys[i], valid[i]), (0, 0, false)),
+                }
+            }
+            assert_eq!(count, expected);
+        }
+
+        let (mut xs, mut ys, mut valid) = (vec![0.0; n], vec![0.0; n], vec![false; n]);
+        projector.gcs_to_grid_slice(&longitudes, &latitudes, &mut xs, &mut ys, &mut valid);
+        for i in 0..n {
+            match projector.gcs_to_grid(longitudes[i], latitudes[i]) {
+                Ok((x, y)) => assert_eq!(
+                    (xs[i].to_bits(), ys[i].to_bits(), valid[i]),
+                    (x.to_bits(), y.to_bits(), true)
+                ),
+                Err(_) => assert!(!valid[i] && xs[i].is_nan() && ys[i].is_nan()),
+            }
+        }
+    }
+
+    #[test]
+    fn continuous_round_trip() {
+        let projector = Projector::dfs();
+        let longitudes: Vec<f64> = (0..100).map(|i| 124.0 + 0.08 * i as f64).collect();
+        let latitudes: Vec<f64> = (0..100).map(|i| 39.0 - 0.06 * i as f64).collect();
+        let (mut xs, mut ys, mut valid) = (vec![0.0; 100], vec![0.0; 100], vec![false; 100]);
+        let count =
+            projector.gcs_to_grid_slice(&longitudes, &latitudes, &mut xs, &mut ys, &mut valid);
+        assert_eq!(count, 100);
+
+        let (mut lons, mut lats) = (vec![0.0; 100], vec![0.0; 100]);
+        projector.grid_to_gcs_slice(&xs, &ys, &mut lons, &mut lats);
+        for i in 0..100 {
+            assert_eq!(
+                projector.gcs_to_grid(longitudes[i], latitudes[i]),
+                Ok((xs[i], ys[i]))
+            );
+            assert!((lons[i] - longitudes[i]).abs() < 1e-9);
+            assert!((lats[i] - latitudes[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn lengths() {
+        let (mut xs, mut ys, mut valid) = ([0; 2], [0; 2], [false; 2]);
+        KmaGrid::from_gcs_slice(&[126.0; 2], &[38.0; 3], &mut xs, &mut ys, &mut valid);
+    }
+}
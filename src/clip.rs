@@ -0,0 +1,100 @@
+// Polygons on the grid plane, in grid coordinates where cell (x, y) covers
+// x - 0.5 ~ x + 0.5 and y - 0.5 ~ y + 0.5
+
+// clips a polygon, convex or not, to a cell by Sutherland-Hodgman. Parts of a
+// concave polygon left apart by the cell are joined along the cell edges, which
+// keeps the area right.
+pub(crate) fn clip_to_cell(polygon: &[(f64, f64)], x: f64, y: f64) -> Vec<(f64, f64)> {
+    let (x0, x1, y0, y1) = (x - 0.5, x + 0.5, y - 0.5, y + 0.5);
+    let mut clipped = polygon.to_vec();
+    clipped = clip_half_plane(&clipped, |p| p.0 - x0, |a, b| crossing_x(a, b, x0));
+    clipped = clip_half_plane(&clipped, |p| x1 - p.0, |a, b| crossing_x(a, b, x1));
+    clipped = clip_half_plane(&clipped, |p| p.1 - y0, |a, b| crossing_y(a, b, y0));
+    clip_half_plane(&clipped, |p| y1 - p.1, |a, b| crossing_y(a, b, y1))
+}
+
+// keeps the part where inside is not negative
+fn clip_half_plane(
+    polygon: &[(f64, f64)],
+    inside: impl Fn((f64, f64)) -> f64,
+    crossing: impl Fn((f64, f64), (f64, f64)) -> (f64, f64),
+) -> Vec<(f64, f64)> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 4);
+    for (index, &current) in polygon.iter().enumerate() {
+        let previous = polygon[(index + polygon.len() - 1) % polygon.len()];
+        match (inside(previous) >= 0.0, inside(current) >= 0.0) {
+            (true, true) => clipped.push(current),
+            (true, false) => clipped.push(crossing(previous, current)),
+            (false, true) => {
+                clipped.push(crossing(previous, current));
+                clipped.push(current);
+            }
+            (false, false) => {}
+        }
+    }
+    clipped
+}
+
+fn crossing_x(a: (f64, f64), b: (f64, f64), x: f64) -> (f64, f64) {
+    (x, a.1 + (b.1 - a.1) * (x - a.0) / (b.0 - a.0))
+}
+
+fn crossing_y(a: (f64, f64), b: (f64, f64), y: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * (y - a.1) / (b.1 - a.1), y)
+}
+
+// by the shoelace formula, positive when counter-clockwise
+pub(crate) fn signed_area(polygon: &[(f64, f64)]) -> f64 {
+    let mut area = 0.0;
+    for (index, &(x, y)) in polygon.iter().enumerate() {
+        let (next_x, next_y) = polygon[(index + 1) % polygon.len()];
+        area += x * next_y - next_x * y;
+    }
+    area * 0.5
+}
+
+// smallest and largest cells overlapping the polygon, as (x0, y0, x1, y1)
+pub(crate) fn cell_range(polygon: &[(f64, f64)]) -> (i32, i32, i32, i32) {
+    let (mut x0, mut y0) = (f64::INFINITY, f64::INFINITY);
+    let (mut x1, mut y1) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for &(x, y) in polygon {
+        (x0, y0) = (x0.min(x), y0.min(y));
+        (x1, y1) = (x1.max(x), y1.max(y));
+    }
+    (
+        (x0 - 0.5).floor() as i32 + 1,
+        (y0 - 0.5).floor() as i32 + 1,
+        (x1 + 0.5).ceil() as i32 - 1,
+        (y1 + 0.5).ceil() as i32 - 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_square() {
+        // a square covering four cells
+        let square = [(0.5, 0.5), (2.5, 0.5), (2.5, 2.5), (0.5, 2.5)];
+        assert_eq!(cell_range(&square), (1, 1, 2, 2));
+        assert!((signed_area(&square) - 4.0).abs() < 1e-12);
+        assert!((signed_area(&clip_to_cell(&square, 1.0, 1.0)) - 1.0).abs() < 1e-12);
+        assert!((signed_area(&clip_to_cell(&square, 2.0, 2.0)) - 1.0).abs() < 1e-12);
+        assert!(clip_to_cell(&square, 4.0, 1.0).is_empty());
+
+        // a concave polygon splitting the cell in two
+        let u_shape = [
+            (0.0, 0.0),
+            (3.0, 0.0),
+            (3.0, 3.0),
+            (1.8, 3.0),
+            (1.8, 0.8),
+            (1.2, 0.8),
+            (1.2, 3.0),
+            (0.0, 3.0),
+        ];
+        let clipped = clip_to_cell(&u_shape, 1.5, 1.5);
+        assert!((signed_area(&clipped) - 0.4).abs() < 1e-12);
+    }
+}
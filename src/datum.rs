@@ -0,0 +1,170 @@
+use crate::ellipsoid::Ellipsoid;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::projection::{DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+
+const ARC_SECOND_TO_RADIAN: f64 = DEGREE_TO_RADIAN / 3600.0;
+
+// Geodetic datum of longitude and latitude
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Datum {
+    // GPS and the KMA grids
+    #[default]
+    Wgs84,
+    // Korea 2000 (KGD2002), on GRS80
+    Grs80,
+    // Korean 1985 on the Bessel 1841 ellipsoid, sharing the origin of the Tokyo datum
+    Korean1985,
+}
+
+// Seven parameter Helmert transformation to WGS84 in the position vector convention
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Helmert {
+    pub tx: f64,    // m
+    pub ty: f64,    // m
+    pub tz: f64,    // m
+    pub rx: f64,    // arc second
+    pub ry: f64,    // arc second
+    pub rz: f64,    // arc second
+    pub scale: f64, // ppm
+}
+
+impl Helmert {
+    // Korean 1985 to WGS84 as published with EPSG:5174 and friends
+    pub const KOREAN_1985_TO_WGS84: Helmert = Helmert {
+        tx: -115.80,
+        ty: 474.99,
+        tz: 674.11,
+        rx: 1.16,
+        ry: -2.31,
+        rz: -1.63,
+        scale: 6.43,
+    };
+
+    // the inverse transformation, exact to the small angle approximation
+    pub fn inverse(&self) -> Helmert {
+        Helmert {
+            tx: -self.tx,
+            ty: -self.ty,
+            tz: -self.tz,
+            rx: -self.rx,
+            ry: -self.ry,
+            rz: -self.rz,
+            scale: -self.scale,
+        }
+    }
+
+    // transforms earth-centred earth-fixed coordinates in metres
+    pub fn apply(&self, (x, y, z): (f64, f64, f64)) -> (f64, f64, f64) {
+        let rx = self.rx * ARC_SECOND_TO_RADIAN;
+        let ry = self.ry * ARC_SECOND_TO_RADIAN;
+        let rz = self.rz * ARC_SECOND_TO_RADIAN;
+        let scale = 1.0 + self.scale * 1e-6;
+        (
+            self.tx + scale * (x - rz * y + ry * z),
+            self.ty + scale * (rz * x + y - rx * z),
+            self.tz + scale * (-ry * x + rx * y + z),
+        )
+    }
+}
+
+impl Datum {
+    pub fn ellipsoid(&self) -> Ellipsoid {
+        match self {
+            Datum::Wgs84 => Ellipsoid::WGS84,
+            Datum::Grs80 => Ellipsoid::GRS80,
+            Datum::Korean1985 => Ellipsoid::BESSEL,
+        }
+    }
+
+    // None when the datum coincides with WGS84 at the metre level
+    pub fn to_wgs84_parameters(&self) -> Option<Helmert> {
+        match self {
+            Datum::Wgs84 | Datum::Grs80 => None,
+            Datum::Korean1985 => Some(Helmert::KOREAN_1985_TO_WGS84),
+        }
+    }
+
+    pub fn to_wgs84(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        self.transform(Datum::Wgs84, longitude, latitude)
+    }
+
+    pub fn from_wgs84(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        Datum::Wgs84.transform(*self, longitude, latitude)
+    }
+
+    // transforms a point on the ellipsoid surface to another datum through WGS84
+    pub fn transform(&self, to: Datum, longitude: f64, latitude: f64) -> (f64, f64) {
+        if *self == to {
+            return (longitude, latitude);
+        }
+
+        let mut xyz = geodetic_to_geocentric(&self.ellipsoid(), longitude, latitude);
+        if let Some(helmert) = self.to_wgs84_parameters() {
+            xyz = helmert.apply(xyz);
+        }
+        if let Some(helmert) = to.to_wgs84_parameters() {
+            xyz = helmert.inverse().apply(xyz);
+        }
+        geocentric_to_geodetic(&to.ellipsoid(), xyz)
+    }
+}
+
+fn geodetic_to_geocentric(ellipsoid: &Ellipsoid, longitude: f64, latitude: f64) -> (f64, f64, f64) {
+    let a = ellipsoid.semi_major_axis * 1000.0;
+    let e2 = ellipsoid.eccentricity().powi(2);
+    let (longitude, latitude) = (longitude * DEGREE_TO_RADIAN, latitude * DEGREE_TO_RADIAN);
+
+    let radius = a / (1.0 - e2 * latitude.sin().powi(2)).sqrt();
+    (
+        radius * latitude.cos() * longitude.cos(),
+        radius * latitude.cos() * longitude.sin(),
+        radius * (1.0 - e2) * latitude.sin(),
+    )
+}
+
+fn geocentric_to_geodetic(ellipsoid: &Ellipsoid, (x, y, z): (f64, f64, f64)) -> (f64, f64) {
+    let a = ellipsoid.semi_major_axis * 1000.0;
+    let e2 = ellipsoid.eccentricity().powi(2);
+    let p = (x.powi(2) + y.powi(2)).sqrt();
+
+    // iterate the latitude together with the ellipsoidal height
+    let mut latitude = z.atan2(p * (1.0 - e2));
+    for _ in 0..10 {
+        let radius = a / (1.0 - e2 * latitude.sin().powi(2)).sqrt();
+        let height = p / latitude.cos() - radius;
+        let next = z.atan2(p * (1.0 - e2 * radius / (radius + height)));
+        let delta = (next - latitude).abs();
+        latitude = next;
+        if delta < 1e-14 {
+            break;
+        }
+    }
+
+    (y.atan2(x) * RADIAN_TO_DEGREE, latitude * RADIAN_TO_DEGREE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn korean_1985_to_wgs84() {
+        let (longitude, latitude) = Datum::Korean1985.to_wgs84(127.0, 37.5);
+        // about 185 m west and 311 m north around Seoul
+        assert!((longitude - 126.997899).abs() < 1e-6);
+        assert!((latitude - 37.502804).abs() < 1e-6);
+
+        let (lon, lat) = Datum::Korean1985.from_wgs84(longitude, latitude);
+        assert!((lon - 127.0).abs() < 1e-7);
+        assert!((lat - 37.5).abs() < 1e-7);
+    }
+
+    #[test]
+    fn same_datum() {
+        assert_eq!(Datum::Wgs84.to_wgs84(127.0, 37.5), (127.0, 37.5));
+        let (longitude, latitude) = Datum::Grs80.to_wgs84(127.0, 37.5);
+        assert!((longitude - 127.0).abs() < 1e-12);
+        assert!((latitude - 37.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::GridError;
+use crate::grid_spec::GridSpec;
+use crate::kma_grid::KmaGrid;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::projection::check_gcs;
+use crate::projector::Projector;
+
+// nodes of the 2 point Gauss-Legendre rule on -0.5 ~ 0.5
+const GAUSS_NODES: [f64; 2] = [-0.288_675_134_594_812_9, 0.288_675_134_594_812_9];
+
+impl Projector {
+    // map scale factor, the length on the grid over the length on the ground
+    pub fn scale_factor(&self, longitude: f64, latitude: f64) -> Result<f64, GridError> {
+        check_gcs(longitude, latitude)?;
+        Ok(self.projected_grid().projection.scale_factor(latitude))
+    }
+
+    // angle in degree from true north clockwise to grid north
+    pub fn convergence_angle(&self, longitude: f64, latitude: f64) -> Result<f64, GridError> {
+        check_gcs(longitude, latitude)?;
+        Ok(self.projected_grid().projection.convergence(longitude))
+    }
+
+    pub fn cell_scale_factor(&self, grid: KmaGrid) -> Result<f64, GridError> {
+        let (longitude, latitude) = self.to_gcs(grid)?;
+        self.scale_factor(longitude, latitude)
+    }
+
+    pub fn cell_convergence_angle(&self, grid: KmaGrid) -> Result<f64, GridError> {
+        let (longitude, latitude) = self.to_gcs(grid)?;
+        self.convergence_angle(longitude, latitude)
+    }
+
+    // ground area of a cell in km², integrating the inverse square of the scale
+    // factor over the cell as the projection is conformal
+    pub fn cell_area(&self, grid: KmaGrid) -> Result<f64, GridError> {
+        self.projected_grid().check_contains(grid)?;
+        let lcc = &self.projected_grid().projection;
+        let mut mean = 0.0;
+        for dx in GAUSS_NODES {
+            for dy in GAUSS_NODES {
+                let (_, latitude) = self.grid_to_gcs(grid.x() as f64 + dx, grid.y() as f64 + dy);
+                mean += 0.25 / lcc.scale_factor(latitude).powi(2);
+            }
+        }
+        Ok(self.spec().grid_length.powi(2) * mean)
+    }
+}
+
+impl GridSpec {
+    pub fn scale_factor(&self, longitude: f64, latitude: f64) -> Result<f64, GridError> {
+        Projector::new(*self).scale_factor(longitude, latitude)
+    }
+
+    pub fn convergence_angle(&self, longitude: f64, latitude: f64) -> Result<f64, GridError> {
+        Projector::new(*self).convergence_angle(longitude, latitude)
+    }
+
+    pub fn cell_scale_factor(&self, grid: KmaGrid) -> Result<f64, GridError> {
+        Projector::new(*self).cell_scale_factor(grid)
+    }
+
+    pub fn cell_convergence_angle(&self, grid: KmaGrid) -> Result<f64, GridError> {
+        Projector::new(*self).cell_convergence_angle(grid)
+    }
+
+    pub fn cell_area(&self, grid: KmaGrid) -> Result<f64, GridError> {
+        Projector::new(*self).cell_area(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_area() {
+        let dfs = Projector::dfs();
+        // cells between the standard parallels are larger on the ground than on the map
+        let seoul = dfs.cell_area(KmaGrid::new(60, 127)).unwrap();
+        let scale_factor = dfs.cell_scale_factor(KmaGrid::new(60, 127)).unwrap();
+        assert!(scale_factor < 1.0);
+        assert!((seoul * scale_factor.powi(2) / 25.0 - 1.0).abs() < 1e-6);
+
+        // a DFS cell is the sum of the 25 cells of a 1 km grid nested in it
+        let fine = Projector::new(GridSpec {
+            grid_length: 1.0,
+            reference_x: 561.0,
+            reference_y: 841.0,
+            nx: 1153,
+            ny: 1441,
+            ..GridSpec::DFS_5KM
+        });
+        let mut sum = 0.0;
+        for x in 644..=648 {
+            for y in 794..=798 {
+                sum += fine.cell_area(KmaGrid::new(x, y)).unwrap();
+            }
+        }
+        assert!((sum - seoul).abs() < 1e-9);
+
+        assert_eq!(
+            dfs.cell_area(KmaGrid::new(150, 1)),
+            Err(GridError::OutOfDomain { x: 150, y: 1 })
+        );
+    }
+
+    #[test]
+    fn convergence_angle() {
+        let dfs = Projector::dfs();
+        assert_eq!(dfs.convergence_angle(126.0, 33.0), Ok(0.0));
+
+        // grid north leans east of true north east of the reference longitude
+        let dokdo = KmaGrid::new(144, 123);
+        let angle = dfs.cell_convergence_angle(dokdo).unwrap();
+        let (longitude, latitude) = dfs.grid_to_gcs(144.0, 123.0);
+        let (north_longitude, north_latitude) = dfs.grid_to_gcs(144.0, 123.1);
+        let east = (north_longitude - longitude) * latitude.to_radians().cos();
+        let bearing = east.atan2(north_latitude - latitude).to_degrees();
+        assert!(angle > 0.0);
+        assert!((bearing - angle).abs() < 0.01);
+
+        assert!(dfs.scale_factor(126.0, f64::NAN).is_err());
+    }
+}
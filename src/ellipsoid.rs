@@ -0,0 +1,46 @@
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+// Reference ellipsoid of the earth, a sphere when the flattening is zero
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    pub semi_major_axis: f64, // km
+    pub flattening: f64,
+}
+
+impl Ellipsoid {
+    pub const GRS80: Ellipsoid = Ellipsoid {
+        semi_major_axis: 6378.137,
+        flattening: 1.0 / 298.257222101,
+    };
+
+    pub const WGS84: Ellipsoid = Ellipsoid {
+        semi_major_axis: 6378.137,
+        flattening: 1.0 / 298.257223563,
+    };
+
+    // the ellipsoid of the Korean 1985 and Tokyo datums
+    pub const BESSEL: Ellipsoid = Ellipsoid {
+        semi_major_axis: 6377.397155,
+        flattening: 1.0 / 299.1528128,
+    };
+
+    // sphere of the KMA grids, radius 6371.00877 km
+    pub const KMA_SPHERE: Ellipsoid = Ellipsoid::sphere(6371.00877);
+
+    pub const fn sphere(radius: f64) -> Ellipsoid {
+        Ellipsoid {
+            semi_major_axis: radius,
+            flattening: 0.0,
+        }
+    }
+
+    pub fn is_sphere(&self) -> bool {
+        self.flattening == 0.0
+    }
+
+    // first eccentricity
+    pub fn eccentricity(&self) -> f64 {
+        (self.flattening * (2.0 - self.flattening)).sqrt()
+    }
+}
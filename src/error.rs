@@ -0,0 +1,79 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridError {
+    // longitude or latitude is NaN or infinite
+    NonFinite { longitude: f64, latitude: f64 },
+    // latitude is outside of -90 ~ 90 degrees, or cannot be projected
+    InvalidLatitude(f64),
+    // the cell (x, y) lies outside the NX x NY domain of the grid
+    OutOfDomain { x: i32, y: i32 },
+    // a distance that is negative or not a finite number
+    InvalidDistance(f64),
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::NonFinite {
+                longitude,
+                latitude,
+            } => write!(
+                f,
+                "coordinate ({}, {}) is not a finite number",
+                longitude, latitude
+            ),
+            GridError::InvalidLatitude(latitude) => {
+                write!(f, "latitude {} is out of range", latitude)
+            }
+            GridError::OutOfDomain { x, y } => {
+                write!(f, "grid cell ({}, {}) is outside of the grid domain", x, y)
+            }
+            GridError::InvalidDistance(distance) => {
+                write!(f, "distance {} is not a finite length", distance)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GridError {}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    // the definition is malformed at the given position or token
+    Syntax(String),
+    // the projection is not a Lambert conformal conic
+    UnsupportedProjection(String),
+    // a parameter or unit the grid spec cannot represent
+    UnsupportedParameter(String),
+    // a parameter required for the projection is missing
+    MissingParameter(&'static str),
+    // a parameter whose value is not a number
+    InvalidValue { name: String, value: String },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax(message) => write!(f, "malformed definition: {}", message),
+            ParseError::UnsupportedProjection(projection) => {
+                write!(f, "unsupported projection {}", projection)
+            }
+            ParseError::UnsupportedParameter(parameter) => {
+                write!(f, "unsupported parameter {}", parameter)
+            }
+            ParseError::MissingParameter(parameter) => {
+                write!(f, "missing parameter {}", parameter)
+            }
+            ParseError::InvalidValue { name, value } => {
+                write!(f, "invalid value {} for parameter {}", value, name)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
@@ -0,0 +1,322 @@
+use std::ops::RangeInclusive;
+
+use crate::clip::{cell_range, clip_to_cell, signed_area};
+use crate::error::GridError;
+use crate::kma_grid::KmaGrid;
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::Projection;
+
+// Extent in longitude and latitude, in degree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub west: f64,
+    pub south: f64,
+    pub east: f64,
+    pub north: f64,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, longitude: f64, latitude: f64) -> bool {
+        (self.west..=self.east).contains(&longitude)
+            && (self.south..=self.north).contains(&latitude)
+    }
+
+    fn of_points(points: &[(f64, f64)]) -> BoundingBox {
+        points.iter().fold(
+            BoundingBox {
+                west: f64::INFINITY,
+                south: f64::INFINITY,
+                east: f64::NEG_INFINITY,
+                north: f64::NEG_INFINITY,
+            },
+            |bbox, &(longitude, latitude)| BoundingBox {
+                west: bbox.west.min(longitude),
+                south: bbox.south.min(latitude),
+                east: bbox.east.max(longitude),
+                north: bbox.north.max(latitude),
+            },
+        )
+    }
+}
+
+// Which cells an area on the map selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Coverage {
+    // cells whose centre is inside
+    #[default]
+    Centre,
+    // cells whose footprint overlaps it
+    Footprint,
+}
+
+impl<P: Projection> ProjectedGrid<P> {
+    // corners of a cell counter-clockwise from (x - 0.5, y - 0.5), the lower left
+    pub fn corners(&self, grid: KmaGrid) -> Result<[(f64, f64); 4], GridError> {
+        self.check_contains(grid)?;
+        let (x, y) = (grid.x() as f64, grid.y() as f64);
+        Ok([
+            self.grid_to_gcs(x - 0.5, y - 0.5),
+            self.grid_to_gcs(x + 0.5, y - 0.5),
+            self.grid_to_gcs(x + 0.5, y + 0.5),
+            self.grid_to_gcs(x - 0.5, y + 0.5),
+        ])
+    }
+
+    // closed ring along the cell edges, each divided into the given number of
+    // segments as the edges are curves in longitude and latitude
+    pub fn outline(&self, grid: KmaGrid, segments: usize) -> Result<Vec<(f64, f64)>, GridError> {
+        self.check_contains(grid)?;
+        let segments = segments.max(1);
+        Ok(self.ring(grid, grid, segments, segments))
+    }
+
+    pub fn bounding_box(&self, grid: KmaGrid) -> Result<BoundingBox, GridError> {
+        self.range_bounding_box(grid, grid)
+    }
+
+    // extent of the cells between two opposite corners of a block, inclusive
+    pub fn range_bounding_box(
+        &self,
+        first: KmaGrid,
+        last: KmaGrid,
+    ) -> Result<BoundingBox, GridError> {
+        self.check_contains(first)?;
+        self.check_contains(last)?;
+        let lower = KmaGrid::new(first.x().min(last.x()), first.y().min(last.y()));
+        let upper = KmaGrid::new(first.x().max(last.x()), first.y().max(last.y()));
+
+        // two segments per cell keep the bulge of the edges below a metre on 5 km cells
+        let x_segments = 2 * (upper.x() - lower.x() + 1) as usize;
+        let y_segments = 2 * (upper.y() - lower.y() + 1) as usize;
+        Ok(BoundingBox::of_points(
+            &self.ring(lower, upper, x_segments, y_segments),
+        ))
+    }
+
+    // cells selected by the box, row by row from the lower left. Boxes across
+    // the antimeridian with west > east select nothing.
+    pub fn cells_in_box(
+        &self,
+        bbox: &BoundingBox,
+        coverage: Coverage,
+    ) -> Result<impl Iterator<Item = KmaGrid> + '_, GridError> {
+        let selection = self.box_selection(bbox, coverage)?;
+        let columns = selection.columns.clone();
+        Ok(selection
+            .rows
+            .clone()
+            .flat_map(move |y| columns.clone().map(move |x| KmaGrid::new(x, y)))
+            .filter(move |grid| selection.selects(*grid)))
+    }
+
+    pub(crate) fn box_selection(
+        &self,
+        bbox: &BoundingBox,
+        coverage: Coverage,
+    ) -> Result<BoxSelection<'_, P>, GridError> {
+        let polygon = self.box_on_grid(bbox)?;
+        let (x0, y0, x1, y1) = if polygon.is_empty() {
+            (1, 1, 0, 0)
+        } else {
+            cell_range(&polygon)
+        };
+        Ok(BoxSelection {
+            grid: self,
+            bbox: *bbox,
+            coverage,
+            polygon,
+            columns: x0.max(1)..=x1.min(self.nx),
+            rows: y0.max(1)..=y1.min(self.ny),
+        })
+    }
+
+    // outline of the box in grid coordinates counter-clockwise
+    fn box_on_grid(&self, bbox: &BoundingBox) -> Result<Vec<(f64, f64)>, GridError> {
+        if !(bbox.west <= bbox.east && bbox.south <= bbox.north) {
+            return Ok(Vec::new());
+        }
+        self.ring_on_grid(&[
+            (bbox.west, bbox.south),
+            (bbox.east, bbox.south),
+            (bbox.east, bbox.north),
+            (bbox.west, bbox.north),
+        ])
+    }
+
+    fn ring(
+        &self,
+        lower: KmaGrid,
+        upper: KmaGrid,
+        x_segments: usize,
+        y_segments: usize,
+    ) -> Vec<(f64, f64)> {
+        let (x0, y0) = (lower.x() as f64 - 0.5, lower.y() as f64 - 0.5);
+        let (x1, y1) = (upper.x() as f64 + 0.5, upper.y() as f64 + 0.5);
+        let along = |from: f64, to: f64, step: usize, segments: usize| {
+            from + (to - from) * step as f64 / segments as f64
+        };
+
+        let mut points = Vec::with_capacity(2 * (x_segments + y_segments) + 1);
+        for step in 0..x_segments {
+            points.push(self.grid_to_gcs(along(x0, x1, step, x_segments), y0));
+        }
+        for step in 0..y_segments {
+            points.push(self.grid_to_gcs(x1, along(y0, y1, step, y_segments)));
+        }
+        for step in 0..x_segments {
+            points.push(self.grid_to_gcs(along(x1, x0, step, x_segments), y1));
+        }
+        for step in 0..y_segments {
+            points.push(self.grid_to_gcs(x0, along(y1, y0, step, y_segments)));
+        }
+        points.push(points[0]);
+        points
+    }
+}
+
+// A box on a grid with the cells it may select
+pub(crate) struct BoxSelection<'a, P> {
+    grid: &'a ProjectedGrid<P>,
+    bbox: BoundingBox,
+    coverage: Coverage,
+    polygon: Vec<(f64, f64)>,
+    pub(crate) columns: RangeInclusive<i32>,
+    pub(crate) rows: RangeInclusive<i32>,
+}
+
+impl<P: Projection> BoxSelection<'_, P> {
+    pub(crate) fn selects(&self, grid: KmaGrid) -> bool {
+        let (x, y) = (grid.x() as f64, grid.y() as f64);
+        match self.coverage {
+            Coverage::Centre => {
+                let (longitude, latitude) = self.grid.grid_to_gcs(x, y);
+                self.bbox.contains(longitude, latitude)
+            }
+            Coverage::Footprint => signed_area(&clip_to_cell(&self.polygon, x, y)) > 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_spec::GridSpec;
+
+    const SEOUL: BoundingBox = BoundingBox {
+        west: 126.76,
+        south: 37.41,
+        east: 127.19,
+        north: 37.72,
+    };
+
+    #[test]
+    fn corners_around_centre() {
+        let grid = GridSpec::DFS_5KM.projected_grid();
+        let seoul = KmaGrid::new(60, 127);
+        let (longitude, latitude) = grid.to_gcs(seoul).unwrap();
+        let [lower_left, lower_right, upper_right, upper_left] = grid.corners(seoul).unwrap();
+        assert!(lower_left.0 < longitude && upper_left.0 < longitude);
+        assert!(lower_right.0 > longitude && upper_right.0 > longitude);
+        assert!(lower_left.1 < latitude && lower_right.1 < latitude);
+        assert!(upper_left.1 > latitude && upper_right.1 > latitude);
+
+        // neighbouring cells share their corners
+        let [east_lower_left, _, _, east_upper_left] = grid.corners(KmaGrid::new(61, 127)).unwrap();
+        assert_eq!(east_lower_left, lower_right);
+        assert_eq!(east_upper_left, upper_right);
+
+        let outline = grid.outline(seoul, 1).unwrap();
+        assert_eq!(
+            outline,
+            vec![lower_left, lower_right, upper_right, upper_left, lower_left]
+        );
+        assert_eq!(grid.outline(seoul, 8).unwrap().len(), 33);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let grid = GridSpec::DFS_5KM.projected_grid();
+        let seoul = KmaGrid::new(60, 127);
+        let bbox = grid.bounding_box(seoul).unwrap();
+        let (longitude, latitude) = grid.to_gcs(seoul).unwrap();
+        assert!(bbox.contains(longitude, latitude));
+        for (longitude, latitude) in grid.corners(seoul).unwrap() {
+            assert!(bbox.contains(longitude, latitude));
+        }
+
+        // the top edge of the domain bulges north of its corners between them
+        let domain = grid
+            .range_bounding_box(KmaGrid::new(149, 253), KmaGrid::new(1, 1))
+            .unwrap();
+        let [_, _, upper_right, _] = grid.corners(KmaGrid::new(149, 253)).unwrap();
+        let [_, _, _, upper_left] = grid.corners(KmaGrid::new(1, 253)).unwrap();
+        assert!(domain.north > upper_right.1.max(upper_left.1) + 0.03);
+        for (x, y) in [(1, 1), (75, 253), (149, 127)] {
+            let (longitude, latitude) = grid.to_gcs(KmaGrid::new(x, y)).unwrap();
+            assert!(domain.contains(longitude, latitude));
+        }
+
+        assert_eq!(
+            grid.bounding_box(KmaGrid::new(0, 1)),
+            Err(GridError::OutOfDomain { x: 0, y: 1 })
+        );
+    }
+
+    #[test]
+    fn cells_in_box() {
+        let grid = GridSpec::DFS_5KM.projected_grid();
+        let centres: Vec<_> = grid
+            .cells_in_box(&SEOUL, Coverage::Centre)
+            .unwrap()
+            .collect();
+        let footprints: Vec<_> = grid
+            .cells_in_box(&SEOUL, Coverage::Footprint)
+            .unwrap()
+            .collect();
+        assert!(centres.contains(&KmaGrid::new(60, 127)));
+        assert!(centres.len() > 40 && footprints.len() > centres.len());
+
+        // the same cells as going through the whole domain
+        for y in 1..=grid.ny {
+            for x in 1..=grid.nx {
+                let cell = KmaGrid::new(x, y);
+                let (longitude, latitude) = grid.to_gcs(cell).unwrap();
+                assert_eq!(centres.contains(&cell), SEOUL.contains(longitude, latitude));
+                let corners = grid.corners(cell).unwrap();
+                if corners.iter().any(|&(lon, lat)| SEOUL.contains(lon, lat)) {
+                    assert!(footprints.contains(&cell));
+                }
+                if centres.contains(&cell) {
+                    assert!(footprints.contains(&cell));
+                }
+            }
+        }
+
+        // a box inside a single cell
+        let (longitude, latitude) = grid.to_gcs(KmaGrid::new(60, 127)).unwrap();
+        let small = BoundingBox {
+            west: longitude - 0.001,
+            south: latitude - 0.001,
+            east: longitude + 0.001,
+            north: latitude + 0.001,
+        };
+        let cells: Vec<_> = grid
+            .cells_in_box(&small, Coverage::Footprint)
+            .unwrap()
+            .collect();
+        assert_eq!(cells, vec![KmaGrid::new(60, 127)]);
+
+        let outside = BoundingBox {
+            west: 140.0,
+            south: 20.0,
+            east: 150.0,
+            north: 25.0,
+        };
+        assert_eq!(
+            grid.cells_in_box(&outside, Coverage::Footprint)
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::ellipsoid::Ellipsoid;
+use crate::error::GridError;
+use crate::kma_grid::KmaGrid;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::projection::{check_gcs, DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+
+impl Ellipsoid {
+    // distance in km and initial bearing in degree clockwise from north from one
+    // point to another, by the haversine formula on a sphere and Vincenty's
+    // inverse formula on an ellipsoid
+    pub fn inverse(
+        &self,
+        longitude1: f64,
+        latitude1: f64,
+        longitude2: f64,
+        latitude2: f64,
+    ) -> Result<(f64, f64), GridError> {
+        check_gcs(longitude1, latitude1)?;
+        check_gcs(longitude2, latitude2)?;
+        let (phi1, phi2) = (latitude1 * DEGREE_TO_RADIAN, latitude2 * DEGREE_TO_RADIAN);
+        let lambda = (longitude2 - longitude1) * DEGREE_TO_RADIAN;
+
+        let (distance, bearing) = if self.is_sphere() {
+            haversine(self.semi_major_axis, phi1, phi2, lambda)
+        } else {
+            vincenty(self, phi1, phi2, lambda)
+        };
+        Ok((distance, (bearing * RADIAN_TO_DEGREE).rem_euclid(360.0)))
+    }
+
+    pub fn distance(
+        &self,
+        longitude1: f64,
+        latitude1: f64,
+        longitude2: f64,
+        latitude2: f64,
+    ) -> Result<f64, GridError> {
+        let (distance, _) = self.inverse(longitude1, latitude1, longitude2, latitude2)?;
+        Ok(distance)
+    }
+
+    pub fn bearing(
+        &self,
+        longitude1: f64,
+        latitude1: f64,
+        longitude2: f64,
+        latitude2: f64,
+    ) -> Result<f64, GridError> {
+        let (_, bearing) = self.inverse(longitude1, latitude1, longitude2, latitude2)?;
+        Ok(bearing)
+    }
+}
+
+fn haversine(radius: f64, phi1: f64, phi2: f64, lambda: f64) -> (f64, f64) {
+    let h = ((phi2 - phi1) * 0.5).sin().powi(2)
+        + phi1.cos() * phi2.cos() * (lambda * 0.5).sin().powi(2);
+    let distance = 2.0 * radius * h.sqrt().min(1.0).asin();
+    let bearing = (lambda.sin() * phi2.cos())
+        .atan2(phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * lambda.cos());
+    (distance, bearing)
+}
+
+// https://en.wikipedia.org/wiki/Vincenty%27s_formulae#Inverse_problem
+// converges to 1e-12 radian except for nearly antipodal points
+fn vincenty(ellipsoid: &Ellipsoid, phi1: f64, phi2: f64, longitude: f64) -> (f64, f64) {
+    let a = ellipsoid.semi_major_axis;
+    let f = ellipsoid.flattening;
+    let b = a * (1.0 - f);
+    let (sin_u1, cos_u1) = ((1.0 - f) * phi1.tan()).atan().sin_cos();
+    let (sin_u2, cos_u2) = ((1.0 - f) * phi2.tan()).atan().sin_cos();
+
+    let mut lambda = longitude;
+    let (mut sin_sigma, mut cos_sigma, mut sigma) = (0.0, 1.0, 0.0);
+    let (mut cos2_alpha, mut cos_2sigma_m) = (1.0, 0.0);
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        // the same point
+        if sin_sigma == 0.0 {
+            return (0.0, 0.0);
+        }
+        cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        cos2_alpha = 1.0 - sin_alpha.powi(2);
+        // both points on the equator
+        cos_2sigma_m = if cos2_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        };
+        let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+        let next = longitude
+            + (1.0 - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m + c * cos_sigma * (2.0 * cos_2sigma_m.powi(2) - 1.0)));
+        let delta = (next - lambda).abs();
+        lambda = next;
+        if delta < 1e-12 {
+            break;
+        }
+    }
+
+    let u2 = cos2_alpha * (a * a - b * b) / (b * b);
+    let big_a = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+    let big_b = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+    let delta_sigma = big_b
+        * sin_sigma
+        * (cos_2sigma_m
+            + big_b / 4.0
+                * (cos_sigma * (2.0 * cos_2sigma_m.powi(2) - 1.0)
+                    - big_b / 6.0
+                        * cos_2sigma_m
+                        * (4.0 * sin_sigma.powi(2) - 3.0)
+                        * (4.0 * cos_2sigma_m.powi(2) - 3.0)));
+    let distance = b * big_a * (sigma - delta_sigma);
+
+    let (sin_lambda, cos_lambda) = lambda.sin_cos();
+    let bearing = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+    (distance, bearing)
+}
+
+// Distances in km and bearings in degree between cell centres of GridSpec::DFS_5KM
+// and points, on the KMA sphere; use Ellipsoid::inverse for other figures
+impl KmaGrid {
+    pub fn distance_to(self, other: KmaGrid) -> Result<f64, GridError> {
+        let (longitude, latitude) = other.to_gcs()?;
+        self.distance_to_gcs(longitude, latitude)
+    }
+
+    pub fn bearing_to(self, other: KmaGrid) -> Result<f64, GridError> {
+        let (longitude, latitude) = other.to_gcs()?;
+        self.bearing_to_gcs(longitude, latitude)
+    }
+
+    pub fn distance_to_gcs(self, longitude: f64, latitude: f64) -> Result<f64, GridError> {
+        let (lon, lat) = self.to_gcs()?;
+        Ellipsoid::KMA_SPHERE.distance(lon, lat, longitude, latitude)
+    }
+
+    pub fn bearing_to_gcs(self, longitude: f64, latitude: f64) -> Result<f64, GridError> {
+        let (lon, lat) = self.to_gcs()?;
+        Ellipsoid::KMA_SPHERE.bearing(lon, lat, longitude, latitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projector::Projector;
+
+    fn dms(degree: f64, minute: f64, second: f64) -> f64 {
+        degree.signum() * (degree.abs() + minute / 60.0 + second / 3600.0)
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = Ellipsoid::KMA_SPHERE;
+        let quarter = sphere.semi_major_axis * std::f64::consts::FRAC_PI_2;
+        let (distance, bearing) = sphere.inverse(126.0, 0.0, 126.0, 90.0).unwrap();
+        assert!((distance - quarter).abs() < 1e-9);
+        assert!(bearing.abs() < 1e-9);
+        let (distance, bearing) = sphere.inverse(0.0, 0.0, -90.0, 0.0).unwrap();
+        assert!((distance - quarter).abs() < 1e-9);
+        assert!((bearing - 270.0).abs() < 1e-9);
+        assert_eq!(sphere.inverse(127.0, 37.0, 127.0, 37.0), Ok((0.0, 0.0)));
+        assert!(sphere.distance(127.0, 91.0, 127.0, 37.0).is_err());
+    }
+
+    #[test]
+    fn vincenty() {
+        // Flinders Peak to Buninyong, the worked example of Geoscience Australia
+        let (distance, bearing) = Ellipsoid::GRS80
+            .inverse(
+                dms(144.0, 25.0, 29.5244),
+                dms(-37.0, 57.0, 3.7203),
+                dms(143.0, 55.0, 35.3839),
+                dms(-37.0, 39.0, 10.1561),
+            )
+            .unwrap();
+        assert!((distance - 54.972271).abs() < 1e-6);
+        assert!((bearing - dms(306.0, 52.0, 5.37)).abs() < 0.01 / 3600.0);
+        assert_eq!(
+            Ellipsoid::WGS84.inverse(127.0, 37.0, 127.0, 37.0),
+            Ok((0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn between_cells() {
+        let seoul = KmaGrid::new(60, 127);
+        let east = KmaGrid::new(61, 127);
+        let k = Projector::dfs().cell_scale_factor(seoul).unwrap();
+        assert!((seoul.distance_to(east).unwrap() - 5.0 / k).abs() < 1e-3);
+
+        // the x axis turns clockwise from east by the convergence angle
+        let angle = Projector::dfs().cell_convergence_angle(seoul).unwrap();
+        assert!((seoul.bearing_to(east).unwrap() - (90.0 + angle)).abs() < 0.01);
+
+        let (longitude, latitude) = seoul.to_gcs().unwrap();
+        assert_eq!(seoul.distance_to_gcs(longitude, latitude), Ok(0.0));
+        assert!(seoul.distance_to(KmaGrid::new(0, 0)).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::ellipsoid::Ellipsoid;
+use crate::error::GridError;
+use crate::kma_grid::KmaGrid;
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::LambertConformalConic;
+use crate::projector::Projector;
+
+// Parameters of a grid on the Lambert conformal conic projection. Conversions
+// given a GridSpec compute its projection constants on every call, a Projector
+// computes them once to convert many points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridSpec {
+    pub grid_length: f64,         // km
+    pub ellipsoid: Ellipsoid,     // figure of the earth
+    pub standard_parallel1: f64,  // degree
+    pub standard_parallel2: f64,  // degree
+    pub reference_longitude: f64, // degree
+    pub reference_latitude: f64,  // degree
+    pub reference_x: f64,         // x coordinate of the reference grid
+    pub reference_y: f64,         // y coordinate of the reference grid
+    pub nx: i32,                  // number of grids along the x axis
+    pub ny: i32,                  // number of grids along the y axis
+}
+
+impl GridSpec {
+    // 5 km grid of the short-range digital forecast (DFS, 동네예보)
+    pub const DFS_5KM: GridSpec = GridSpec {
+        grid_length: 5.0,
+        ellipsoid: Ellipsoid::KMA_SPHERE,
+        standard_parallel1: 30.0,
+        standard_parallel2: 60.0,
+        reference_longitude: 126.0,
+        reference_latitude: 38.0,
+        reference_x: 43.0,
+        reference_y: 136.0,
+        nx: 149,
+        ny: 253,
+    };
+
+    // 1.5 km grid of the local data assimilation and prediction system (LDAPS),
+    // whose first grid point is at (121.834, 32.2569)
+    pub const LDAPS_1_5KM: GridSpec = GridSpec {
+        grid_length: 1.5,
+        ellipsoid: Ellipsoid::sphere(6371.229),
+        standard_parallel1: 30.0,
+        standard_parallel2: 60.0,
+        reference_longitude: 126.0,
+        reference_latitude: 38.0,
+        reference_x: 259.679113,
+        reference_y: 412.239424,
+        nx: 602,
+        ny: 781,
+    };
+
+    // whether the grid lies in 1 ~ nx, 1 ~ ny
+    pub fn contains(&self, grid: &KmaGrid) -> bool {
+        (1..=self.nx).contains(&grid.x()) && (1..=self.ny).contains(&grid.y())
+    }
+
+    // the grid in km on the Lambert conformal conic plane of the spec
+    pub fn projected_grid(&self) -> ProjectedGrid<LambertConformalConic> {
+        ProjectedGrid {
+            projection: LambertConformalConic::new(
+                self.ellipsoid,
+                self.standard_parallel1,
+                self.standard_parallel2,
+                self.reference_longitude,
+                self.reference_latitude,
+            ),
+            dx: self.grid_length,
+            dy: self.grid_length,
+            reference_x: self.reference_x,
+            reference_y: self.reference_y,
+            nx: self.nx,
+            ny: self.ny,
+        }
+    }
+
+    // unrounded grid coordinates of a point, where integers are grid centres
+    pub fn gcs_to_grid(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        Projector::new(*self).gcs_to_grid(longitude, latitude)
+    }
+
+    // longitude and latitude of unrounded grid coordinates
+    pub fn grid_to_gcs(&self, x: f64, y: f64) -> (f64, f64) {
+        Projector::new(*self).grid_to_gcs(x, y)
+    }
+}
+
+impl Default for GridSpec {
+    fn default() -> Self {
+        GridSpec::DFS_5KM
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_dfs() {
+        assert_eq!(GridSpec::default(), GridSpec::DFS_5KM);
+    }
+
+    #[test]
+    fn continuous_grid() {
+        let spec = GridSpec::DFS_5KM;
+        let (x, y) = spec.gcs_to_grid(126.978, 37.5665).unwrap();
+        assert!((x - 59.808485).abs() < 1e-6);
+        assert!((y - 126.707702).abs() < 1e-6);
+
+        let (longitude, latitude) = spec.grid_to_gcs(x, y);
+        assert!((longitude - 126.978).abs() < 1e-9);
+        assert!((latitude - 37.5665).abs() < 1e-9);
+
+        // the fractional part survives a round trip
+        let (longitude, latitude) = spec.grid_to_gcs(12.25, 200.75);
+        let (x, y) = spec.gcs_to_grid(longitude, latitude).unwrap();
+        assert!((x - 12.25).abs() < 1e-9);
+        assert!((y - 200.75).abs() < 1e-9);
+    }
+}
@@ -1,104 +1,153 @@
-use std::f64;
+use crate::datum::Datum;
+use crate::error::GridError;
+#[cfg(feature = "std")]
+use crate::footprint::{BoundingBox, Coverage};
+use crate::grid_spec::GridSpec;
+use crate::projection::TransverseMercator;
+use crate::projector::Projector;
+#[cfg(feature = "std")]
+use crate::raster::Polygon;
 
-const GRID_LENGTH: f64 = 5.0; // km
-const EARTH_RADIUS_IN_GRID: f64 = 6371.00877 / GRID_LENGTH; // km
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KmaGrid {
+    x: i32, // x coordinate of the grid, 1 ~ nx
+    y: i32, // y coordinate of the grid, 1 ~ ny
+}
 
-const DEGREE_TO_RADIAN: f64 = 0.017453293; // pi / 180
-const RADIAN_TO_DEGREE: f64 = 57.29577951; // 180 / pi
+// what to do with a point whose grid falls outside the nx x ny domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfRange {
+    // fail with GridError::OutOfDomain
+    #[default]
+    Error,
+    // snap to the nearest grid on the domain edge
+    Clamp,
+    // keep the signed grid coordinates as they are
+    Unbounded,
+}
 
-const STANDARD_PARALLEL1: f64 = 30.0 * DEGREE_TO_RADIAN; // radian
-const STANDARD_PARALLEL2: f64 = 60.0 * DEGREE_TO_RADIAN; // radian
+impl KmaGrid {
+    pub fn new(x: i32, y: i32) -> KmaGrid {
+        KmaGrid { x, y }
+    }
 
-const REFERENCE_LONGITUDE: f64 = 126.0 * DEGREE_TO_RADIAN; // radian
-const REFERENCE_LATITUDE: f64 = 38.0 * DEGREE_TO_RADIAN; // radian
+    pub fn x(&self) -> i32 {
+        self.x
+    }
 
-const REFERENCE_X: u8 = 43; // x coordinate of the reference grid
-const REFERENCE_Y: u8 = 136; // y coordinate of the reference grid
+    pub fn y(&self) -> i32 {
+        self.y
+    }
 
-pub struct KmaGrid {
-    x: u8, // x coordinate of the grid, 0 ~ 149
-    y: u8, // y coordinate of the grid, 0 ~ 253
-}
+    // whether the grid lies in the domain of GridSpec::DFS_5KM
+    pub fn is_in_domain(&self) -> bool {
+        GridSpec::DFS_5KM.contains(self)
+    }
 
-struct LccConstants {
-    n: f64,
-    f: f64,
-    rho_zero: f64,
-}
+    // panics where try_from_gcs would return an error
+    pub fn from_gcs(longitude: f64, latitude: f64) -> KmaGrid {
+        match KmaGrid::try_from_gcs(longitude, latitude) {
+            Ok(grid) => grid,
+            Err(error) => panic!("{}", error),
+        }
+    }
 
-impl LccConstants {
-    fn get_constants() -> Self {
-        let n = (STANDARD_PARALLEL1.cos() / STANDARD_PARALLEL2.cos()).ln()
-            / (f64::tan(f64::consts::PI * 0.25 + 0.5 * STANDARD_PARALLEL2)
-                / f64::tan(f64::consts::PI * 0.25 + 0.5 * STANDARD_PARALLEL1))
-            .ln();
-
-        let f = (f64::consts::PI * 0.25 + 0.5 * STANDARD_PARALLEL1)
-            .tan()
-            .powf(n)
-            * STANDARD_PARALLEL1.cos()
-            / n;
-
-        let rho_zero = EARTH_RADIUS_IN_GRID * f
-            / (f64::consts::PI * 0.25 + 0.5 * REFERENCE_LATITUDE)
-                .tan()
-                .powf(n);
-        LccConstants { n, f, rho_zero }
+    pub fn try_from_gcs(longitude: f64, latitude: f64) -> Result<KmaGrid, GridError> {
+        KmaGrid::try_from_gcs_with(longitude, latitude, OutOfRange::Error)
     }
-}
 
-impl KmaGrid {
-    // https://en.wikipedia.org/wiki/Lambert_conformal_conic_projection#Transformation
-    // Lambert conformal conic projection
+    pub fn try_from_gcs_with(
+        longitude: f64,
+        latitude: f64,
+        out_of_range: OutOfRange,
+    ) -> Result<KmaGrid, GridError> {
+        Projector::dfs().from_gcs_with(longitude, latitude, out_of_range)
+    }
 
-    pub fn from_gcs(longitude: f64, latitude: f64) -> KmaGrid {
-        let LccConstants { n, f, rho_zero } = LccConstants::get_constants();
-        let rho = EARTH_RADIUS_IN_GRID * f
-            / (f64::consts::PI * 0.25 + 0.5 * latitude * DEGREE_TO_RADIAN)
-                .tan()
-                .powf(n);
-
-        let theta = {
-            let raw_theta = longitude * DEGREE_TO_RADIAN - REFERENCE_LONGITUDE;
-            if raw_theta > f64::consts::PI {
-                raw_theta - 2.0 * f64::consts::PI
-            } else if raw_theta < -f64::consts::PI {
-                raw_theta + 2.0 * f64::consts::PI
-            } else {
-                raw_theta
-            }
-        };
+    pub fn try_from_gcs_in(
+        spec: &GridSpec,
+        longitude: f64,
+        latitude: f64,
+        out_of_range: OutOfRange,
+    ) -> Result<KmaGrid, GridError> {
+        Projector::new(*spec).from_gcs_with(longitude, latitude, out_of_range)
+    }
+
+    // converts a point given in another datum such as Korean 1985
+    pub fn try_from_gcs_on(
+        datum: Datum,
+        longitude: f64,
+        latitude: f64,
+    ) -> Result<KmaGrid, GridError> {
+        Projector::dfs().from_gcs_on(datum, longitude, latitude, OutOfRange::Error)
+    }
 
-        let x = (rho * (theta * n).sin() + (REFERENCE_X as f64)).round() as u8;
-        let y = (rho_zero - rho * (theta * n).cos() + (REFERENCE_Y as f64)).round() as u8;
+    // converts a point in UTM-K or Korea 2000 TM belt metres
+    pub fn try_from_tm(
+        tm: &TransverseMercator,
+        easting: f64,
+        northing: f64,
+    ) -> Result<KmaGrid, GridError> {
+        Projector::dfs().from_tm(tm, easting, northing, OutOfRange::Error)
+    }
 
-        return KmaGrid { x, y };
+    pub fn to_gcs(self) -> Result<(f64, f64), GridError> {
+        Projector::dfs().to_gcs(self)
     }
 
-    pub fn to_gcs(self: Self) -> (f64, f64) {
-        let LccConstants { n, f, rho_zero } = LccConstants::get_constants();
-        let xn = (self.x - REFERENCE_X) as f64;
-        let yn = rho_zero - (self.y - REFERENCE_Y) as f64;
+    pub fn to_gcs_in(self, spec: &GridSpec) -> Result<(f64, f64), GridError> {
+        Projector::new(*spec).to_gcs(self)
+    }
 
-        let ra = (xn.powi(2) + yn.powi(2)).sqrt();
+    pub fn to_gcs_on(self, datum: Datum) -> Result<(f64, f64), GridError> {
+        Projector::dfs().to_gcs_on(datum, self)
+    }
 
-        let latitude_radian =
-            2.0 * (EARTH_RADIUS_IN_GRID * f / ra).powf(1.0 / n).atan() - f64::consts::PI * 0.5;
+    pub fn to_tm(self, tm: &TransverseMercator) -> Result<(f64, f64), GridError> {
+        Projector::dfs().to_tm(tm, self)
+    }
 
-        let theta = if n.abs() == 0.0 {
-            0.0
-        } else if yn.abs() == 0.0 {
-            f64::consts::PI * 0.5
-        } else {
-            yn.atan2(xn)
-        };
+    // longitude and latitude of the cell corners, counter-clockwise from the lower left
+    #[cfg(feature = "std")]
+    pub fn corners(self) -> Result<[(f64, f64); 4], GridError> {
+        Projector::dfs().corners(self)
+    }
 
-        let longitude_radian = theta / n + REFERENCE_LONGITUDE;
+    // closed ring of the cell outline with each edge divided into segments
+    #[cfg(feature = "std")]
+    pub fn outline(self, segments: usize) -> Result<Vec<(f64, f64)>, GridError> {
+        Projector::dfs().outline(self, segments)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn bounding_box(self) -> Result<BoundingBox, GridError> {
+        Projector::dfs().bounding_box(self)
+    }
+
+    // cells selected by a box in longitude and latitude, row by row from the lower left
+    #[cfg(feature = "std")]
+    pub fn cells_in_box(
+        bbox: &BoundingBox,
+        coverage: Coverage,
+    ) -> Result<impl Iterator<Item = KmaGrid>, GridError> {
+        Projector::dfs().cells_in_box(bbox, coverage)
+    }
 
-        return (
-            longitude_radian * RADIAN_TO_DEGREE,
-            latitude_radian * RADIAN_TO_DEGREE,
-        );
+    // cells overlapping a polygon with the covered fraction of each
+    #[cfg(feature = "std")]
+    pub fn polygon_cells(polygon: &Polygon) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        Projector::dfs().polygon_cells(polygon)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn multipolygon_cells(polygons: &[Polygon]) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        Projector::dfs().multipolygon_cells(polygons)
+    }
+
+    // cells a polyline passes through in order, with the length in km inside each
+    #[cfg(feature = "std")]
+    pub fn polyline_cells(line: &[(f64, f64)]) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        Projector::dfs().polyline_cells(line)
     }
 }
 
@@ -106,6 +155,11 @@ impl KmaGrid {
 mod tests {
     use super::*;
 
+    const NX: i32 = GridSpec::DFS_5KM.nx;
+    const NY: i32 = GridSpec::DFS_5KM.ny;
+    const REFERENCE_X: i32 = 43;
+    const REFERENCE_Y: i32 = 136;
+
     mod convert_to_xy {
         use super::*;
 
@@ -115,5 +169,166 @@ mod tests {
             assert_eq!(grid.x, REFERENCE_X);
             assert_eq!(grid.y, REFERENCE_Y);
         }
+
+        #[test]
+        fn non_finite() {
+            assert!(matches!(
+                KmaGrid::try_from_gcs(f64::NAN, 38.0),
+                Err(GridError::NonFinite { .. })
+            ));
+            assert!(matches!(
+                KmaGrid::try_from_gcs(126.0, f64::INFINITY),
+                Err(GridError::NonFinite { .. })
+            ));
+        }
+
+        #[test]
+        fn invalid_latitude() {
+            assert_eq!(
+                KmaGrid::try_from_gcs(126.0, 91.0),
+                Err(GridError::InvalidLatitude(91.0))
+            );
+            assert_eq!(
+                KmaGrid::try_from_gcs(126.0, -90.0),
+                Err(GridError::InvalidLatitude(-90.0))
+            );
+        }
+
+        #[test]
+        fn longitude_turns() {
+            let seoul = KmaGrid::from_gcs(127.0, 37.5);
+            for turns in [-3.0, -2.0, -1.0, 1.0, 2.0, 5.0] {
+                assert_eq!(
+                    KmaGrid::try_from_gcs(127.0 + 360.0 * turns, 37.5),
+                    Ok(seoul)
+                );
+            }
+            // 270.0977 E, on the other side of the earth
+            assert!(matches!(
+                KmaGrid::try_from_gcs(990.0977, 37.5),
+                Err(GridError::OutOfDomain { .. })
+            ));
+        }
+
+        #[test]
+        fn out_of_domain() {
+            // Tokyo
+            let (longitude, latitude) = (139.69, 35.69);
+            let error = KmaGrid::try_from_gcs(longitude, latitude).unwrap_err();
+            let GridError::OutOfDomain { x, y } = error else {
+                panic!("unexpected error: {:?}", error);
+            };
+            assert!(x > NX);
+
+            let clamped =
+                KmaGrid::try_from_gcs_with(longitude, latitude, OutOfRange::Clamp).unwrap();
+            assert_eq!(clamped, KmaGrid::new(NX, y.clamp(1, NY)));
+
+            let unbounded =
+                KmaGrid::try_from_gcs_with(longitude, latitude, OutOfRange::Unbounded).unwrap();
+            assert_eq!(unbounded, KmaGrid::new(x, y));
+            assert!(!unbounded.is_in_domain());
+        }
+
+        #[test]
+        fn korean_1985_datum() {
+            // 0.03 grid north of the edge between y = 126 and 127 in WGS84
+            let (longitude, latitude) = GridSpec::DFS_5KM.grid_to_gcs(60.0, 126.53);
+            let (bessel_longitude, bessel_latitude) =
+                Datum::Korean1985.from_wgs84(longitude, latitude);
+
+            assert_eq!(
+                KmaGrid::try_from_gcs_on(Datum::Korean1985, bessel_longitude, bessel_latitude),
+                Ok(KmaGrid::new(60, 127))
+            );
+            // taken as WGS84 the same numbers fall about 300 m south into the neighbour
+            assert_eq!(
+                KmaGrid::try_from_gcs(bessel_longitude, bessel_latitude),
+                Ok(KmaGrid::new(60, 126))
+            );
+        }
+
+        #[test]
+        fn utm_k() {
+            // Seoul City Hall
+            let tm = TransverseMercator::UTM_K;
+            let grid = KmaGrid::try_from_tm(&tm, 953_901.165, 1_952_032.081);
+            assert_eq!(grid, Ok(KmaGrid::new(60, 127)));
+
+            let (easting, northing) = grid.unwrap().to_tm(&tm).unwrap();
+            assert_eq!(KmaGrid::try_from_tm(&tm, easting, northing), grid);
+            // the grid centre is within half a diagonal of 5 km grid
+            let distance =
+                ((easting - 953_901.165).powi(2) + (northing - 1_952_032.081).powi(2)).sqrt();
+            assert!(distance < 2500.0 * 2f64.sqrt());
+        }
+
+        #[test]
+        #[should_panic]
+        fn from_gcs_panics_out_of_domain() {
+            KmaGrid::from_gcs(139.69, 35.69);
+        }
+    }
+
+    mod convert_to_gcs {
+        use super::*;
+
+        #[test]
+        fn reference_grid() {
+            let (longitude, latitude) = KmaGrid::new(REFERENCE_X, REFERENCE_Y).to_gcs().unwrap();
+            assert!((longitude - 126.0).abs() < 1e-6);
+            assert!((latitude - 38.0).abs() < 1e-6);
+        }
+
+        #[test]
+        fn west_and_south_of_reference() {
+            let (longitude, latitude) = KmaGrid::new(1, 1).to_gcs().unwrap();
+            assert!(longitude < 126.0);
+            assert!(latitude < 38.0);
+        }
+
+        #[test]
+        fn round_trip_every_grid() {
+            for x in 1..=NX {
+                for y in 1..=NY {
+                    let grid = KmaGrid::new(x, y);
+                    let (longitude, latitude) = grid.to_gcs().unwrap();
+                    assert_eq!(KmaGrid::from_gcs(longitude, latitude), grid);
+                }
+            }
+        }
+
+        #[test]
+        fn custom_spec() {
+            // the DFS projection on a 1 km grid sharing the same south-west corner
+            let spec = GridSpec {
+                grid_length: 1.0,
+                reference_x: 211.0,
+                reference_y: 676.0,
+                nx: 741,
+                ny: 1261,
+                ..GridSpec::DFS_5KM
+            };
+            let grid = KmaGrid::new(10, 20);
+            let (longitude, latitude) = grid.to_gcs_in(&spec).unwrap();
+            assert_eq!(
+                KmaGrid::try_from_gcs_in(&spec, longitude, latitude, OutOfRange::Error),
+                Ok(grid)
+            );
+            assert_eq!(
+                KmaGrid::try_from_gcs(longitude, latitude),
+                Ok(KmaGrid::new(3, 5))
+            );
+        }
+
+        #[test]
+        fn out_of_domain() {
+            for (x, y) in [(0, 1), (1, 0), (NX + 1, 1), (1, NY + 1)] {
+                assert_eq!(
+                    KmaGrid::new(x, y).to_gcs(),
+                    Err(GridError::OutOfDomain { x, y })
+                );
+            }
+        }
     }
 }
@@ -1,2 +1,53 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("kma-grid needs the std feature, or the libm feature without std");
+
+mod batch;
+#[cfg(feature = "std")]
+mod clip;
+mod datum;
+mod distortion;
+mod ellipsoid;
+mod error;
+#[cfg(feature = "std")]
+mod footprint;
+mod geodesic;
+mod grid_spec;
 mod kma_grid;
-pub use crate::kma_grid::KmaGrid;
+#[cfg(not(any(feature = "std", test)))]
+mod math;
+#[cfg(feature = "std")]
+mod metadata;
+#[cfg(feature = "std")]
+mod neighbourhood;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod parse;
+mod projected_grid;
+mod projection;
+mod projector;
+#[cfg(feature = "std")]
+mod raster;
+#[cfg(feature = "std")]
+mod traverse;
+mod wind;
+pub use crate::datum::{Datum, Helmert};
+pub use crate::ellipsoid::Ellipsoid;
+pub use crate::error::GridError;
+#[cfg(feature = "std")]
+pub use crate::error::ParseError;
+#[cfg(feature = "std")]
+pub use crate::footprint::{BoundingBox, Coverage};
+pub use crate::grid_spec::GridSpec;
+pub use crate::kma_grid::{KmaGrid, OutOfRange};
+#[cfg(feature = "std")]
+pub use crate::metadata::CfValue;
+pub use crate::projected_grid::ProjectedGrid;
+pub use crate::projection::{
+    LambertConformalConic, LatLon, Mercator, PolarStereographic, Projection, TransverseMercator,
+};
+pub use crate::projector::Projector;
+#[cfg(feature = "std")]
+pub use crate::raster::Polygon;
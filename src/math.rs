@@ -0,0 +1,99 @@
+// Methods of f64 that live in std, here from libm so that the same calls work
+// without std. powi and rem_euclid follow what std does, to give the same values.
+// Unit tests always build with std and its methods, so this module is covered
+// by the tests in tests/ run with --no-default-features --features libm.
+pub(crate) trait Float {
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn tan(self) -> f64;
+    fn asin(self) -> f64;
+    fn atan(self) -> f64;
+    fn atan2(self, other: f64) -> f64;
+    fn sin_cos(self) -> (f64, f64);
+    fn exp(self) -> f64;
+    fn ln(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn sqrt(self) -> f64;
+    fn round(self) -> f64;
+    fn rem_euclid(self, rhs: f64) -> f64;
+}
+
+impl Float for f64 {
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+
+    fn asin(self) -> f64 {
+        libm::asin(self)
+    }
+
+    fn atan(self) -> f64 {
+        libm::atan(self)
+    }
+
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+
+    fn sin_cos(self) -> (f64, f64) {
+        (libm::sin(self), libm::cos(self))
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    // by squaring, as the powi of compiler-rt
+    fn powi(self, n: i32) -> f64 {
+        let (mut base, mut exponent, mut power) = (self, n, 1.0);
+        loop {
+            if exponent & 1 != 0 {
+                power *= base;
+            }
+            exponent /= 2;
+            if exponent == 0 {
+                break;
+            }
+            base *= base;
+        }
+        if n < 0 {
+            1.0 / power
+        } else {
+            power
+        }
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let remainder = self % rhs;
+        if remainder < 0.0 {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use std::fmt::Write;
+
+use crate::ellipsoid::Ellipsoid;
+use crate::grid_spec::GridSpec;
+
+// Projected coordinates of the definitions below are in metres, with the centre of
+// grid (1, 1) at the origin, so the false origin is where KMA's converter puts it.
+
+const DEGREE_UNIT: &str = r#"ANGLEUNIT["degree",0.0174532925199433]"#;
+const METRE_UNIT: &str = r#"LENGTHUNIT["metre",1]"#;
+
+// Value of a netCDF attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum CfValue {
+    Text(String),
+    Number(f64),
+    Numbers(Vec<f64>),
+}
+
+// prints metres without the noise of the km to m multiplication
+fn metres(km: f64) -> f64 {
+    (km * 1e9).round() / 1e6
+}
+
+impl GridSpec {
+    pub fn false_easting(&self) -> f64 {
+        metres((self.reference_x - 1.0) * self.grid_length)
+    }
+
+    pub fn false_northing(&self) -> f64 {
+        metres((self.reference_y - 1.0) * self.grid_length)
+    }
+
+    fn proj_ellipsoid(&self) -> String {
+        let ellipsoid = self.ellipsoid;
+        if ellipsoid == Ellipsoid::GRS80 {
+            "+ellps=GRS80".to_string()
+        } else if ellipsoid == Ellipsoid::WGS84 {
+            "+ellps=WGS84".to_string()
+        } else if ellipsoid.is_sphere() {
+            format!("+R={}", metres(ellipsoid.semi_major_axis))
+        } else {
+            format!(
+                "+a={} +rf={}",
+                metres(ellipsoid.semi_major_axis),
+                1.0 / ellipsoid.flattening
+            )
+        }
+    }
+
+    // PROJ.4 definition of the projected coordinates
+    pub fn to_proj_string(&self) -> String {
+        format!(
+            "+proj=lcc +lat_1={} +lat_2={} +lat_0={} +lon_0={} +x_0={} +y_0={} {} +units=m +no_defs",
+            self.standard_parallel1,
+            self.standard_parallel2,
+            self.reference_latitude,
+            self.reference_longitude,
+            self.false_easting(),
+            self.false_northing(),
+            self.proj_ellipsoid(),
+        )
+    }
+
+    // OGC WKT2:2019 definition of the projected coordinates
+    pub fn to_wkt(&self) -> String {
+        let ellipsoid = self.ellipsoid;
+        let inverse_flattening = if ellipsoid.is_sphere() {
+            0.0
+        } else {
+            1.0 / ellipsoid.flattening
+        };
+        // GRS80 alone does not tell which datum, Korea 2000 or another
+        let (datum, ellipsoid_name) = if ellipsoid == Ellipsoid::GRS80 {
+            ("unknown", "GRS 1980")
+        } else if ellipsoid == Ellipsoid::WGS84 {
+            ("World Geodetic System 1984", "WGS 84")
+        } else if ellipsoid.is_sphere() {
+            ("unknown", "sphere")
+        } else {
+            ("unknown", "unknown")
+        };
+
+        let mut wkt = String::new();
+        write!(wkt, r#"PROJCRS["KMA Lambert conformal conic","#).unwrap();
+        write!(
+            wkt,
+            r#"BASEGEOGCRS["unknown",DATUM["{}",ELLIPSOID["{}",{},{},{}]],PRIMEM["Greenwich",0,{}]],"#,
+            datum,
+            ellipsoid_name,
+            metres(ellipsoid.semi_major_axis),
+            inverse_flattening,
+            METRE_UNIT,
+            DEGREE_UNIT,
+        )
+        .unwrap();
+        write!(
+            wkt,
+            r#"CONVERSION["unnamed",METHOD["Lambert Conic Conformal (2SP)",ID["EPSG",9802]],"#
+        )
+        .unwrap();
+        for (name, value, unit, id) in [
+            (
+                "Latitude of false origin",
+                self.reference_latitude,
+                DEGREE_UNIT,
+                8821,
+            ),
+            (
+                "Longitude of false origin",
+                self.reference_longitude,
+                DEGREE_UNIT,
+                8822,
+            ),
+            (
+                "Latitude of 1st standard parallel",
+                self.standard_parallel1,
+                DEGREE_UNIT,
+                8823,
+            ),
+            (
+                "Latitude of 2nd standard parallel",
+                self.standard_parallel2,
+                DEGREE_UNIT,
+                8824,
+            ),
+            (
+                "Easting at false origin",
+                self.false_easting(),
+                METRE_UNIT,
+                8826,
+            ),
+            (
+                "Northing at false origin",
+                self.false_northing(),
+                METRE_UNIT,
+                8827,
+            ),
+        ] {
+            write!(
+                wkt,
+                r#"PARAMETER["{}",{},{},ID["EPSG",{}]],"#,
+                name, value, unit, id
+            )
+            .unwrap();
+        }
+        wkt.pop();
+        write!(
+            wkt,
+            r#"],CS[Cartesian,2],AXIS["easting (X)",east,ORDER[1],{}],AXIS["northing (Y)",north,ORDER[2],{}]]"#,
+            METRE_UNIT, METRE_UNIT,
+        )
+        .unwrap();
+        wkt
+    }
+
+    // GDAL affine geotransform of a north-up raster, whose first row is y = ny
+    pub fn geo_transform(&self) -> [f64; 6] {
+        let length = metres(self.grid_length);
+        [
+            -0.5 * length,
+            length,
+            0.0,
+            (self.ny as f64 - 0.5) * length,
+            0.0,
+            -length,
+        ]
+    }
+
+    // attributes of a CF conventions grid mapping variable
+    pub fn cf_grid_mapping(&self) -> Vec<(&'static str, CfValue)> {
+        let mut attributes = vec![
+            (
+                "grid_mapping_name",
+                CfValue::Text("lambert_conformal_conic".to_string()),
+            ),
+            (
+                "standard_parallel",
+                CfValue::Numbers(vec![self.standard_parallel1, self.standard_parallel2]),
+            ),
+            (
+                "longitude_of_central_meridian",
+                CfValue::Number(self.reference_longitude),
+            ),
+            (
+                "latitude_of_projection_origin",
+                CfValue::Number(self.reference_latitude),
+            ),
+            ("false_easting", CfValue::Number(self.false_easting())),
+            ("false_northing", CfValue::Number(self.false_northing())),
+        ];
+        if self.ellipsoid.is_sphere() {
+            attributes.push((
+                "earth_radius",
+                CfValue::Number(metres(self.ellipsoid.semi_major_axis)),
+            ));
+        } else {
+            attributes.push((
+                "semi_major_axis",
+                CfValue::Number(metres(self.ellipsoid.semi_major_axis)),
+            ));
+            attributes.push((
+                "inverse_flattening",
+                CfValue::Number(1.0 / self.ellipsoid.flattening),
+            ));
+        }
+        attributes.push(("crs_wkt", CfValue::Text(self.to_wkt())));
+        attributes.push(("proj4_params", CfValue::Text(self.to_proj_string())));
+        let geo_transform = self.geo_transform().map(|value| value.to_string());
+        attributes.push(("GeoTransform", CfValue::Text(geo_transform.join(" "))));
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kma_grid::KmaGrid;
+    use crate::projection::{LambertConformalConic, Projection};
+
+    #[test]
+    fn dfs_proj_string() {
+        assert_eq!(
+            GridSpec::DFS_5KM.to_proj_string(),
+            "+proj=lcc +lat_1=30 +lat_2=60 +lat_0=38 +lon_0=126 +x_0=210000 +y_0=675000 \
+             +R=6371008.77 +units=m +no_defs"
+        );
+        let spec = GridSpec {
+            ellipsoid: Ellipsoid::GRS80,
+            ..GridSpec::DFS_5KM
+        };
+        assert!(spec.to_proj_string().contains("+ellps=GRS80"));
+    }
+
+    #[test]
+    fn dfs_wkt() {
+        let wkt = GridSpec::DFS_5KM.to_wkt();
+        assert!(wkt.starts_with("PROJCRS["));
+        assert!(wkt.contains(r#"ELLIPSOID["sphere",6371008.77,0,LENGTHUNIT["metre",1]]"#));
+        assert!(wkt.contains(r#"PARAMETER["Easting at false origin",210000,"#));
+        assert!(wkt.contains(r#"PARAMETER["Northing at false origin",675000,"#));
+        assert_eq!(wkt.matches('[').count(), wkt.matches(']').count());
+
+        let spec = GridSpec {
+            ellipsoid: Ellipsoid::GRS80,
+            ..GridSpec::DFS_5KM
+        };
+        assert!(spec
+            .to_wkt()
+            .contains(r#"DATUM["unknown",ELLIPSOID["GRS 1980",6378137,298.257222101,"#));
+    }
+
+    #[test]
+    fn geo_transform_matches_grid_centres() {
+        // pixel centres through the emitted definition land on the same grid centres
+        for spec in [GridSpec::DFS_5KM, GridSpec::LDAPS_1_5KM] {
+            let [x_origin, dx, _, y_origin, _, dy] = spec.geo_transform();
+            let lcc = LambertConformalConic::new(
+                spec.ellipsoid,
+                spec.standard_parallel1,
+                spec.standard_parallel2,
+                spec.reference_longitude,
+                spec.reference_latitude,
+            );
+            for (column, row) in [(0, 0), (42, 117), (spec.nx - 1, spec.ny - 1)] {
+                let easting = x_origin + (column as f64 + 0.5) * dx;
+                let northing = y_origin + (row as f64 + 0.5) * dy;
+                let (longitude, latitude) = lcc.inverse(
+                    (easting - spec.false_easting()) / 1000.0,
+                    (northing - spec.false_northing()) / 1000.0,
+                );
+
+                let grid = KmaGrid::new(column + 1, spec.ny - row);
+                let (lon, lat) = grid.to_gcs_in(&spec).unwrap();
+                assert!((lon - longitude).abs() < 1e-9);
+                assert!((lat - latitude).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn cf_attributes() {
+        let attributes = GridSpec::DFS_5KM.cf_grid_mapping();
+        let get = |name| {
+            attributes
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(
+            get("standard_parallel"),
+            Some(CfValue::Numbers(vec![30.0, 60.0]))
+        );
+        assert_eq!(get("earth_radius"), Some(CfValue::Number(6371008.77)));
+        assert_eq!(
+            get("GeoTransform"),
+            Some(CfValue::Text("-2500 5000 0 1262500 0 -5000".to_string()))
+        );
+    }
+}
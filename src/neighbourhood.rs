@@ -0,0 +1,182 @@
+use crate::error::GridError;
+use crate::grid_spec::GridSpec;
+use crate::kma_grid::KmaGrid;
+
+// Cells around a cell, leaving out the ones outside of GridSpec::DFS_5KM
+impl KmaGrid {
+    // west, east, south and north
+    pub fn neighbours4(self) -> Vec<KmaGrid> {
+        clip([(-1, 0), (1, 0), (0, -1), (0, 1)].map(|(dx, dy)| self.offset(dx, dy)))
+    }
+
+    // counter-clockwise from the lower left
+    pub fn neighbours8(self) -> Vec<KmaGrid> {
+        self.ring(1)
+    }
+
+    // cells at the Chebyshev distance of radius, counter-clockwise from the lower left
+    pub fn ring(self, radius: u32) -> Vec<KmaGrid> {
+        if radius == 0 {
+            return clip([self]);
+        }
+        // each side runs over the part of its 2 radius cells inside the domain
+        let (x, y, r) = (i64::from(self.x()), i64::from(self.y()), i64::from(radius));
+        let (nx, ny) = (GridSpec::DFS_5KM.nx, GridSpec::DFS_5KM.ny);
+        let inside = |value: i64, n: i32| (1..=i64::from(n)).contains(&value);
+        let span = |low: i64, high: i64, n: i32| low.max(1)..=high.min(i64::from(n));
+        let cell = |x: i64, y: i64| KmaGrid::new(x as i32, y as i32);
+
+        let bottom = span(x - r, x + r - 1, nx)
+            .filter(|_| inside(y - r, ny))
+            .map(|cx| cell(cx, y - r));
+        let right = span(y - r, y + r - 1, ny)
+            .filter(|_| inside(x + r, nx))
+            .map(|cy| cell(x + r, cy));
+        let top = span(x - r + 1, x + r, nx)
+            .rev()
+            .filter(|_| inside(y + r, ny))
+            .map(|cx| cell(cx, y + r));
+        let left = span(y - r + 1, y + r, ny)
+            .rev()
+            .filter(|_| inside(x - r, nx))
+            .map(|cy| cell(x - r, cy));
+        bottom.chain(right).chain(top).chain(left).collect()
+    }
+
+    // cells whose centres are within the great-circle distance in km from the
+    // centre of this cell on the KMA sphere, row by row from the lower left
+    pub fn within_distance(self, distance: f64) -> Result<Vec<KmaGrid>, GridError> {
+        let (longitude, latitude) = self.to_gcs()?;
+        if !(distance.is_finite() && distance >= 0.0) {
+            return Err(GridError::InvalidDistance(distance));
+        }
+
+        // the domain lies between the standard parallels where k < 1, so the
+        // cells within the distance are no more than distance / grid_length apart
+        let spec = GridSpec::DFS_5KM;
+        let radius = (distance / spec.grid_length)
+            .min(f64::from(spec.nx.max(spec.ny)))
+            .floor() as i32
+            + 1;
+        let columns = (self.x() - radius).max(1)..=(self.x() + radius).min(spec.nx);
+        let rows = (self.y() - radius).max(1)..=(self.y() + radius).min(spec.ny);
+
+        let mut within = Vec::new();
+        for y in rows {
+            for x in columns.clone() {
+                let cell = KmaGrid::new(x, y);
+                if cell.distance_to_gcs(longitude, latitude)? <= distance {
+                    within.push(cell);
+                }
+            }
+        }
+        Ok(within)
+    }
+
+    fn offset(self, dx: i32, dy: i32) -> KmaGrid {
+        KmaGrid::new(self.x() + dx, self.y() + dy)
+    }
+}
+
+fn clip(cells: impl IntoIterator<Item = KmaGrid>) -> Vec<KmaGrid> {
+    cells
+        .into_iter()
+        .filter(|grid| GridSpec::DFS_5KM.contains(grid))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let seoul = KmaGrid::new(60, 127);
+        assert_eq!(
+            seoul.neighbours4(),
+            [(59, 127), (61, 127), (60, 126), (60, 128)].map(|(x, y)| KmaGrid::new(x, y))
+        );
+        assert_eq!(
+            seoul.neighbours8(),
+            [
+                (59, 126),
+                (60, 126),
+                (61, 126),
+                (61, 127),
+                (61, 128),
+                (60, 128),
+                (59, 128),
+                (59, 127)
+            ]
+            .map(|(x, y)| KmaGrid::new(x, y))
+        );
+        assert_eq!(seoul.ring(0), vec![seoul]);
+        assert_eq!(seoul.ring(3).len(), 24);
+
+        // corners of the domain
+        assert_eq!(KmaGrid::new(1, 1).neighbours4().len(), 2);
+        assert_eq!(KmaGrid::new(149, 253).neighbours8().len(), 3);
+        assert_eq!(KmaGrid::new(1, 1).ring(2).len(), 5);
+        assert!(KmaGrid::new(0, 0).ring(0).is_empty());
+    }
+
+    #[test]
+    fn within_distance() {
+        let seoul = KmaGrid::new(60, 127);
+        // the neighbours are a bit more than 5 km away on the ground, as k < 1
+        assert_eq!(seoul.within_distance(5.0), Ok(vec![seoul]));
+        assert_eq!(seoul.within_distance(5.2).map(|cells| cells.len()), Ok(5));
+        assert_eq!(seoul.within_distance(0.0), Ok(vec![seoul]));
+
+        let cells = seoul.within_distance(30.0).unwrap();
+        for cell in &cells {
+            let (dx, dy) = (cell.x() - seoul.x(), cell.y() - seoul.y());
+            assert!(f64::from(dx * dx + dy * dy).sqrt() * 5.0 <= 30.0);
+        }
+        assert!(cells.contains(&KmaGrid::new(65, 128)));
+
+        assert_eq!(
+            KmaGrid::new(1, 1)
+                .within_distance(5.2)
+                .map(|cells| cells.len()),
+            Ok(3)
+        );
+        assert!(KmaGrid::new(0, 1).within_distance(5.0).is_err());
+        assert!(matches!(
+            seoul.within_distance(f64::NAN),
+            Err(GridError::InvalidDistance(_))
+        ));
+        assert!(seoul.within_distance(-1.0).is_err());
+        assert_eq!(seoul.within_distance(1e7).unwrap().len(), 149 * 253);
+    }
+
+    #[test]
+    fn within_distance_on_the_sphere() {
+        // the same cells as measuring the distance to every cell of the domain
+        for (centre, distance) in [((60, 127), 300.0), ((75, 20), 200.0), ((1, 253), 120.0)] {
+            let centre = KmaGrid::new(centre.0, centre.1);
+            let mut expected = Vec::new();
+            for y in 1..=253 {
+                for x in 1..=149 {
+                    let cell = KmaGrid::new(x, y);
+                    if cell.distance_to(centre).unwrap() <= distance {
+                        expected.push(cell);
+                    }
+                }
+            }
+            assert_eq!(centre.within_distance(distance), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn far_rings() {
+        let seoul = KmaGrid::new(60, 127);
+        assert!(seoul.ring(u32::MAX).is_empty());
+        assert!(seoul.ring(1 << 31).is_empty());
+        // only the bottom and top sides cross the domain
+        let bottom = (1..=149).map(|x| KmaGrid::new(x, 1));
+        let top = (1..=149).rev().map(|x| KmaGrid::new(x, 253));
+        assert_eq!(seoul.ring(126), bottom.chain(top).collect::<Vec<_>>());
+        assert_eq!(KmaGrid::new(i32::MIN, 1).ring(1).len(), 0);
+    }
+}
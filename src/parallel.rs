@@ -0,0 +1,333 @@
+use rayon::prelude::*;
+
+use crate::batch::check_lengths;
+use crate::error::GridError;
+use crate::footprint::{BoundingBox, Coverage};
+use crate::kma_grid::{KmaGrid, OutOfRange};
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::Projection;
+use crate::projector::Projector;
+use crate::raster::Polygon;
+
+// Parallel versions of the batch conversions and the cell enumerations, giving
+// the same results in the same order as the sequential ones. The slices are cut
+// into chunks, each converted by the sequential code.
+
+const CHUNK: usize = 4096;
+
+impl<P: Projection + Sync> ProjectedGrid<P> {
+    pub fn par_from_gcs_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        out_of_range: OutOfRange,
+        xs: &mut [i32],
+        ys: &mut [i32],
+        valid: &mut [bool],
+    ) -> usize {
+        check_lengths(&[
+            longitudes.len(),
+            latitudes.len(),
+            xs.len(),
+            ys.len(),
+            valid.len(),
+        ]);
+        longitudes
+            .par_chunks(CHUNK)
+            .zip(latitudes.par_chunks(CHUNK))
+            .zip(xs.par_chunks_mut(CHUNK))
+            .zip(ys.par_chunks_mut(CHUNK))
+            .zip(valid.par_chunks_mut(CHUNK))
+            .map(|((((longitudes, latitudes), xs), ys), valid)| {
+                self.from_gcs_slice(longitudes, latitudes, out_of_range, xs, ys, valid)
+            })
+            .sum()
+    }
+
+    pub fn par_to_gcs_slice(
+        &self,
+        xs: &[i32],
+        ys: &[i32],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        check_lengths(&[
+            xs.len(),
+            ys.len(),
+            longitudes.len(),
+            latitudes.len(),
+            valid.len(),
+        ]);
+        xs.par_chunks(CHUNK)
+            .zip(ys.par_chunks(CHUNK))
+            .zip(longitudes.par_chunks_mut(CHUNK))
+            .zip(latitudes.par_chunks_mut(CHUNK))
+            .zip(valid.par_chunks_mut(CHUNK))
+            .map(|((((xs, ys), longitudes), latitudes), valid)| {
+                self.to_gcs_slice(xs, ys, longitudes, latitudes, valid)
+            })
+            .sum()
+    }
+
+    pub fn par_gcs_to_grid_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        xs: &mut [f64],
+        ys: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        check_lengths(&[
+            longitudes.len(),
+            latitudes.len(),
+            xs.len(),
+            ys.len(),
+            valid.len(),
+        ]);
+        longitudes
+            .par_chunks(CHUNK)
+            .zip(latitudes.par_chunks(CHUNK))
+            .zip(xs.par_chunks_mut(CHUNK))
+            .zip(ys.par_chunks_mut(CHUNK))
+            .zip(valid.par_chunks_mut(CHUNK))
+            .map(|((((longitudes, latitudes), xs), ys), valid)| {
+                self.gcs_to_grid_slice(longitudes, latitudes, xs, ys, valid)
+            })
+            .sum()
+    }
+
+    pub fn par_grid_to_gcs_slice(
+        &self,
+        xs: &[f64],
+        ys: &[f64],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+    ) {
+        check_lengths(&[xs.len(), ys.len(), longitudes.len(), latitudes.len()]);
+        xs.par_chunks(CHUNK)
+            .zip(ys.par_chunks(CHUNK))
+            .zip(longitudes.par_chunks_mut(CHUNK))
+            .zip(latitudes.par_chunks_mut(CHUNK))
+            .for_each(|(((xs, ys), longitudes), latitudes)| {
+                self.grid_to_gcs_slice(xs, ys, longitudes, latitudes)
+            });
+    }
+
+    // rows of cells are selected in parallel
+    pub fn par_cells_in_box(
+        &self,
+        bbox: &BoundingBox,
+        coverage: Coverage,
+    ) -> Result<Vec<KmaGrid>, GridError> {
+        let selection = self.box_selection(bbox, coverage)?;
+        Ok(selection
+            .rows
+            .clone()
+            .into_par_iter()
+            .flat_map_iter(|y| {
+                let selection = &selection;
+                selection
+                    .columns
+                    .clone()
+                    .map(move |x| KmaGrid::new(x, y))
+                    .filter(move |grid| selection.selects(*grid))
+            })
+            .collect())
+    }
+
+    pub fn par_polygon_cells(&self, polygon: &Polygon) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        self.par_multipolygon_cells(std::slice::from_ref(polygon))
+    }
+
+    pub fn par_multipolygon_cells(
+        &self,
+        polygons: &[Polygon],
+    ) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        let polygons = self.polygons_on_grid(polygons)?;
+        Ok(polygons
+            .rows
+            .clone()
+            .into_par_iter()
+            .flat_map_iter(|y| polygons.row(y))
+            .collect())
+    }
+}
+
+impl Projector {
+    pub fn par_from_gcs_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        out_of_range: OutOfRange,
+        xs: &mut [i32],
+        ys: &mut [i32],
+        valid: &mut [bool],
+    ) -> usize {
+        self.projected_grid()
+            .par_from_gcs_slice(longitudes, latitudes, out_of_range, xs, ys, valid)
+    }
+
+    pub fn par_to_gcs_slice(
+        &self,
+        xs: &[i32],
+        ys: &[i32],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        self.projected_grid()
+            .par_to_gcs_slice(xs, ys, longitudes, latitudes, valid)
+    }
+
+    pub fn par_gcs_to_grid_slice(
+        &self,
+        longitudes: &[f64],
+        latitudes: &[f64],
+        xs: &mut [f64],
+        ys: &mut [f64],
+        valid: &mut [bool],
+    ) -> usize {
+        self.projected_grid()
+            .par_gcs_to_grid_slice(longitudes, latitudes, xs, ys, valid)
+    }
+
+    pub fn par_grid_to_gcs_slice(
+        &self,
+        xs: &[f64],
+        ys: &[f64],
+        longitudes: &mut [f64],
+        latitudes: &mut [f64],
+    ) {
+        self.projected_grid()
+            .par_grid_to_gcs_slice(xs, ys, longitudes, latitudes)
+    }
+
+    pub fn par_cells_in_box(
+        &self,
+        bbox: &BoundingBox,
+        coverage: Coverage,
+    ) -> Result<Vec<KmaGrid>, GridError> {
+        self.projected_grid().par_cells_in_box(bbox, coverage)
+    }
+
+    pub fn par_polygon_cells(&self, polygon: &Polygon) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        self.projected_grid().par_polygon_cells(polygon)
+    }
+
+    pub fn par_multipolygon_cells(
+        &self,
+        polygons: &[Polygon],
+    ) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        self.projected_grid().par_multipolygon_cells(polygons)
+    }
+}
+
+impl KmaGrid {
+    // from_gcs_slice in parallel
+    pub fn par_from_gcs_slice(
+        longitudes: &[f64],
+        latitudes: &[f64],
+        xs: &mut [i32],
+        ys: &mut [i32],
+        valid: &mut [bool],
+    ) -> usize {
+        Projector::dfs().par_from_gcs_slice(longitudes, latitudes, OutOfRange::Error, xs, ys, valid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(values: &[f64]) -> Vec<u64> {
+        values.iter().map(|value| value.to_bits()).collect()
+    }
+
+    #[test]
+    fn same_as_sequential() {
+        let projector = Projector::dfs();
+        // several chunks and a part of one, some points off the grid or invalid
+        let n = 5 * CHUNK + 17;
+        let longitudes: Vec<f64> = (0..n).map(|i| 120.0 + 14.0 * i as f64 / n as f64).collect();
+        let mut latitudes: Vec<f64> = (0..n).map(|i| 30.0 + (i % 997) as f64 * 0.012).collect();
+        latitudes[100] = f64::NAN;
+        latitudes[2 * CHUNK + 5] = 91.0;
+
+        let (mut xs, mut ys, mut valid) = (vec![0; n], vec![0; n], vec![false; n]);
+        let (mut par_xs, mut par_ys, mut par_valid) = (vec![0; n], vec![0; n], vec![false; n]);
+        let count = KmaGrid::from_gcs_slice(&longitudes, &latitudes, &mut xs, &mut ys, &mut valid);
+        let par_count = KmaGrid::par_from_gcs_slice(
+            &longitudes,
+            &latitudes,
+            &mut par_xs,
+            &mut par_ys,
+            &mut par_valid,
+        );
+        assert_eq!(
+            (count, &xs, &ys, &valid),
+            (par_count, &par_xs, &par_ys, &par_valid)
+        );
+        assert!(count > 0 && count < n);
+
+        // NaN for the invalid elements, compared by bits
+        let (mut lons, mut lats, mut valid) = (vec![0.0; n], vec![0.0; n], vec![false; n]);
+        let (mut par_lons, mut par_lats, mut par_valid) =
+            (vec![0.0; n], vec![0.0; n], vec![false; n]);
+        let count = projector.to_gcs_slice(&xs, &ys, &mut lons, &mut lats, &mut valid);
+        let par_count =
+            projector.par_to_gcs_slice(&xs, &ys, &mut par_lons, &mut par_lats, &mut par_valid);
+        assert_eq!((count, &valid), (par_count, &par_valid));
+        assert_eq!(bits(&lons), bits(&par_lons));
+        assert_eq!(bits(&lats), bits(&par_lats));
+
+        let (mut fx, mut fy, mut valid) = (vec![0.0; n], vec![0.0; n], vec![false; n]);
+        let (mut par_fx, mut par_fy, mut par_valid) = (vec![0.0; n], vec![0.0; n], vec![false; n]);
+        let count =
+            projector.gcs_to_grid_slice(&longitudes, &latitudes, &mut fx, &mut fy, &mut valid);
+        let par_count = projector.par_gcs_to_grid_slice(
+            &longitudes,
+            &latitudes,
+            &mut par_fx,
+            &mut par_fy,
+            &mut par_valid,
+        );
+        assert_eq!((count, &valid), (par_count, &par_valid));
+        assert_eq!(bits(&fx), bits(&par_fx));
+        assert_eq!(bits(&fy), bits(&par_fy));
+
+        let (mut lons, mut lats) = (vec![0.0; n], vec![0.0; n]);
+        let (mut par_lons, mut par_lats) = (vec![0.0; n], vec![0.0; n]);
+        projector.grid_to_gcs_slice(&fx, &fy, &mut lons, &mut lats);
+        projector.par_grid_to_gcs_slice(&fx, &fy, &mut par_lons, &mut par_lats);
+        assert_eq!(bits(&lons), bits(&par_lons));
+        assert_eq!(bits(&lats), bits(&par_lats));
+    }
+
+    #[test]
+    fn same_cells() {
+        let projector = Projector::dfs();
+        let bbox = BoundingBox {
+            west: 126.5,
+            south: 35.0,
+            east: 128.5,
+            north: 37.8,
+        };
+        for coverage in [Coverage::Centre, Coverage::Footprint] {
+            let cells: Vec<_> = projector.cells_in_box(&bbox, coverage).unwrap().collect();
+            assert_eq!(projector.par_cells_in_box(&bbox, coverage), Ok(cells));
+        }
+
+        let polygon = Polygon::new(vec![
+            (126.6, 35.1),
+            (128.4, 35.6),
+            (127.9, 37.7),
+            (126.7, 37.2),
+        ])
+        .with_hole(vec![(127.2, 36.0), (127.6, 36.0), (127.4, 36.5)]);
+        assert_eq!(
+            projector.par_polygon_cells(&polygon),
+            projector.polygon_cells(&polygon)
+        );
+    }
+}
@@ -0,0 +1,615 @@
+use crate::ellipsoid::Ellipsoid;
+use crate::error::{GridError, ParseError};
+use crate::grid_spec::GridSpec;
+use crate::projection::DEGREE_TO_RADIAN;
+
+// Both parsers take (0, 0) of the projected metres as the centre of grid (1, 1),
+// as GridSpec::to_proj_string and GridSpec::to_wkt emit them. Grids anchored
+// elsewhere can be moved with GridSpec::with_grid_at.
+
+// Lambert conformal conic parameters read from a definition, angles in degree
+// and lengths in metres
+#[derive(Debug, Default)]
+struct LccDefinition {
+    standard_parallel1: Option<f64>,
+    standard_parallel2: Option<f64>,
+    latitude_of_origin: Option<f64>,
+    central_meridian: Option<f64>,
+    false_easting: f64,
+    false_northing: f64,
+    ellipsoid: Option<Ellipsoid>,
+}
+
+impl LccDefinition {
+    fn into_spec(self, grid_length: f64, nx: i32, ny: i32) -> Result<GridSpec, ParseError> {
+        let standard_parallel1 = self
+            .standard_parallel1
+            .ok_or(ParseError::MissingParameter("standard parallel"))?;
+        Ok(GridSpec {
+            grid_length,
+            ellipsoid: self.ellipsoid.unwrap_or(Ellipsoid::GRS80),
+            standard_parallel1,
+            standard_parallel2: self.standard_parallel2.unwrap_or(standard_parallel1),
+            reference_longitude: self.central_meridian.unwrap_or(0.0),
+            reference_latitude: self.latitude_of_origin.unwrap_or(0.0),
+            reference_x: self.false_easting / 1000.0 / grid_length + 1.0,
+            reference_y: self.false_northing / 1000.0 / grid_length + 1.0,
+            nx,
+            ny,
+        })
+    }
+}
+
+fn number(name: &str, value: &str) -> Result<f64, ParseError> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| ParseError::InvalidValue {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+}
+
+// well known ellipsoids are matched by their defining values so that
+// the parsed spec compares equal to the presets
+fn ellipsoid(semi_major_axis: f64, inverse_flattening: f64) -> Ellipsoid {
+    let semi_major_axis = semi_major_axis / 1000.0;
+    for known in [Ellipsoid::GRS80, Ellipsoid::WGS84, Ellipsoid::BESSEL] {
+        if semi_major_axis == known.semi_major_axis
+            && (inverse_flattening - 1.0 / known.flattening).abs() < 1e-9
+        {
+            return known;
+        }
+    }
+    if inverse_flattening == 0.0 {
+        Ellipsoid::sphere(semi_major_axis)
+    } else {
+        Ellipsoid {
+            semi_major_axis,
+            flattening: 1.0 / inverse_flattening,
+        }
+    }
+}
+
+impl GridSpec {
+    // reads a +proj=lcc definition of a grid with the given grid length in km
+    pub fn from_proj_string(
+        definition: &str,
+        grid_length: f64,
+        nx: i32,
+        ny: i32,
+    ) -> Result<GridSpec, ParseError> {
+        let mut lcc = LccDefinition::default();
+        let mut projection = None;
+        let (mut a, mut b, mut rf, mut f) = (None, None, None, None);
+
+        for token in definition.split_whitespace() {
+            let token = token.strip_prefix('+').unwrap_or(token);
+            let (key, value) = token.split_once('=').unwrap_or((token, ""));
+            match key {
+                "proj" => projection = Some(value.to_string()),
+                "lat_1" => lcc.standard_parallel1 = Some(number(key, value)?),
+                "lat_2" => lcc.standard_parallel2 = Some(number(key, value)?),
+                "lat_0" => lcc.latitude_of_origin = Some(number(key, value)?),
+                "lon_0" => lcc.central_meridian = Some(number(key, value)?),
+                "x_0" => lcc.false_easting = number(key, value)?,
+                "y_0" => lcc.false_northing = number(key, value)?,
+                "k" | "k_0" if number(key, value)? == 1.0 => {}
+                "R" => lcc.ellipsoid = Some(Ellipsoid::sphere(number(key, value)? / 1000.0)),
+                "a" => a = Some(number(key, value)?),
+                "b" => b = Some(number(key, value)?),
+                "rf" => rf = Some(number(key, value)?),
+                "f" => f = Some(number(key, value)?),
+                "ellps" | "datum" => {
+                    lcc.ellipsoid = Some(match (key, value) {
+                        ("ellps", "GRS80") => Ellipsoid::GRS80,
+                        (_, "WGS84") => Ellipsoid::WGS84,
+                        ("ellps", "bessel") => Ellipsoid::BESSEL,
+                        _ => return Err(ParseError::UnsupportedParameter(token.to_string())),
+                    })
+                }
+                "towgs84" if value.split(',').all(|value| number(key, value) == Ok(0.0)) => {}
+                "pm" if value == "greenwich" || value == "0" => {}
+                "axis" if value == "enu" => {}
+                // only change the units of the projected metres
+                "units" | "to_meter" | "no_defs" | "type" | "wktext" => {}
+                _ => return Err(ParseError::UnsupportedParameter(token.to_string())),
+            }
+        }
+
+        match projection.as_deref() {
+            Some("lcc") => {}
+            Some(projection) => {
+                return Err(ParseError::UnsupportedProjection(projection.to_string()))
+            }
+            None => return Err(ParseError::MissingParameter("proj")),
+        }
+        if let Some(a) = a {
+            let inverse_flattening = match (b, rf, f) {
+                (Some(b), _, _) if b != a => a / (a - b),
+                (_, Some(rf), _) => rf,
+                (_, _, Some(f)) if f != 0.0 => 1.0 / f,
+                _ => 0.0,
+            };
+            lcc.ellipsoid = Some(ellipsoid(a, inverse_flattening));
+        }
+
+        lcc.into_spec(grid_length, nx, ny)
+    }
+
+    // reads a WKT1 or WKT2 Lambert conformal conic definition of a grid with the
+    // given grid length in km
+    pub fn from_wkt(wkt: &str, grid_length: f64, nx: i32, ny: i32) -> Result<GridSpec, ParseError> {
+        let root = WktParser::new(wkt).parse()?;
+        if !matches!(root.keyword.as_str(), "PROJCS" | "PROJCRS" | "PROJECTEDCRS") {
+            return Err(ParseError::UnsupportedProjection(root.keyword));
+        }
+
+        // WKT2 keeps the method and parameters in CONVERSION, WKT1 in the root
+        let conversion = root.child("CONVERSION").unwrap_or(&root);
+        let method = conversion
+            .child("METHOD")
+            .or_else(|| conversion.child("PROJECTION"))
+            .and_then(WktNode::name)
+            .ok_or(ParseError::MissingParameter("PROJECTION"))?;
+        let normalized = normalize(method);
+        let one_standard_parallel = normalized.ends_with("1sp");
+        if !(normalized.contains("lambert")
+            && normalized.contains("conformal")
+            && normalized.contains("conic"))
+        {
+            return Err(ParseError::UnsupportedProjection(method.to_string()));
+        }
+
+        let mut lcc = LccDefinition::default();
+        // WKT1 gives the units of lengths once for the whole definition
+        let length_factor = root.child("UNIT").map(WktNode::factor).transpose()?;
+        for parameter in conversion.children("PARAMETER") {
+            let name = parameter.name().unwrap_or_default();
+            let value = parameter.number(1)?;
+            let unit = parameter
+                .child("ANGLEUNIT")
+                .or_else(|| parameter.child("LENGTHUNIT"))
+                .or_else(|| parameter.child("UNIT"))
+                .map(WktNode::factor)
+                .transpose()?;
+            let angle = || -> Result<f64, ParseError> {
+                match unit {
+                    Some(factor) if (factor - DEGREE_TO_RADIAN).abs() > 1e-12 => {
+                        Ok(value * factor / DEGREE_TO_RADIAN)
+                    }
+                    _ => Ok(value),
+                }
+            };
+            let length = value * unit.or(length_factor).unwrap_or(1.0);
+
+            match normalize(name).as_str() {
+                "standardparallel1" | "latitudeof1ststandardparallel" => {
+                    lcc.standard_parallel1 = Some(angle()?)
+                }
+                "standardparallel2" | "latitudeof2ndstandardparallel" => {
+                    lcc.standard_parallel2 = Some(angle()?)
+                }
+                "latitudeoforigin" | "latitudeoffalseorigin" | "latitudeofnaturalorigin" => {
+                    lcc.latitude_of_origin = Some(angle()?)
+                }
+                "centralmeridian" | "longitudeoffalseorigin" | "longitudeofnaturalorigin" => {
+                    lcc.central_meridian = Some(angle()?)
+                }
+                "falseeasting" | "eastingatfalseorigin" => lcc.false_easting = length,
+                "falsenorthing" | "northingatfalseorigin" => lcc.false_northing = length,
+                "scalefactor" | "scalefactoratnaturalorigin" if value == 1.0 => {}
+                _ => return Err(ParseError::UnsupportedParameter(name.to_string())),
+            }
+        }
+        if one_standard_parallel {
+            // tangent at the origin, as the scale factor is one
+            lcc.standard_parallel1 = lcc.latitude_of_origin;
+        }
+
+        if let Some(node) = root.find("ELLIPSOID").or_else(|| root.find("SPHEROID")) {
+            let factor = node
+                .child("LENGTHUNIT")
+                .map(WktNode::factor)
+                .transpose()?
+                .unwrap_or(1.0);
+            lcc.ellipsoid = Some(ellipsoid(node.number(1)? * factor, node.number(2)?));
+        }
+        if let Some(node) = root.find("TOWGS84") {
+            if (0..node.values.len()).any(|index| node.number(index) != Ok(0.0)) {
+                return Err(ParseError::UnsupportedParameter("TOWGS84".to_string()));
+            }
+        }
+
+        lcc.into_spec(grid_length, nx, ny)
+    }
+
+    // moves the grid so that the centre of grid (x, y) is at the point
+    pub fn with_grid_at(
+        self,
+        x: f64,
+        y: f64,
+        longitude: f64,
+        latitude: f64,
+    ) -> Result<GridSpec, GridError> {
+        let (grid_x, grid_y) = self.gcs_to_grid(longitude, latitude)?;
+        Ok(GridSpec {
+            reference_x: self.reference_x + x - grid_x,
+            reference_y: self.reference_y + y - grid_y,
+            ..self
+        })
+    }
+}
+
+// lower case letters and digits only, so that WKT1, WKT2 and ESRI names compare
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum WktValue {
+    Node(WktNode),
+    Text(String),
+    // numbers and enumerations
+    Bare(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct WktNode {
+    keyword: String,
+    values: Vec<WktValue>,
+}
+
+impl WktNode {
+    fn child(&self, keyword: &str) -> Option<&WktNode> {
+        self.values.iter().find_map(|value| match value {
+            WktValue::Node(node) if node.keyword == keyword => Some(node),
+            _ => None,
+        })
+    }
+
+    fn children<'a>(&'a self, keyword: &'a str) -> impl Iterator<Item = &'a WktNode> {
+        self.values.iter().filter_map(move |value| match value {
+            WktValue::Node(node) if node.keyword == keyword => Some(node),
+            _ => None,
+        })
+    }
+
+    // first node of the keyword in the whole tree
+    fn find(&self, keyword: &str) -> Option<&WktNode> {
+        self.values.iter().find_map(|value| match value {
+            WktValue::Node(node) if node.keyword == keyword => Some(node),
+            WktValue::Node(node) => node.find(keyword),
+            _ => None,
+        })
+    }
+
+    fn name(&self) -> Option<&str> {
+        match self.values.first() {
+            Some(WktValue::Text(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn number(&self, index: usize) -> Result<f64, ParseError> {
+        match self.values.get(index) {
+            Some(WktValue::Bare(value)) => number(&self.keyword, value),
+            _ => Err(ParseError::InvalidValue {
+                name: self.keyword.clone(),
+                value: format!("{:?}", self.values.get(index)),
+            }),
+        }
+    }
+
+    // conversion factor of a unit to metres or radians
+    fn factor(&self) -> Result<f64, ParseError> {
+        self.number(1)
+    }
+}
+
+struct WktParser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> WktParser<'a> {
+    fn new(text: &'a str) -> WktParser<'a> {
+        WktParser { text, position: 0 }
+    }
+
+    fn parse(mut self) -> Result<WktNode, ParseError> {
+        let node = self.node()?;
+        self.skip_whitespace();
+        if self.position != self.text.len() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(node)
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::Syntax(format!("{} at {}", message, self.position))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
+        while let Some(c) = self.peek().filter(|&c| predicate(c)) {
+            self.position += c.len_utf8();
+        }
+        &self.text[start..self.position]
+    }
+
+    fn node(&mut self) -> Result<WktNode, ParseError> {
+        self.skip_whitespace();
+        let keyword = self
+            .take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+            .to_ascii_uppercase();
+        if keyword.is_empty() {
+            return Err(self.error("expected a keyword"));
+        }
+        self.skip_whitespace();
+        let close = match self.peek() {
+            Some('[') => ']',
+            Some('(') => ')',
+            _ => return Err(self.error("expected [")),
+        };
+        self.position += 1;
+
+        let mut values = Vec::new();
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(c) if c == close => {
+                    self.position += 1;
+                    return Ok(WktNode { keyword, values });
+                }
+                _ => return Err(self.error("expected , or ]")),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<WktValue, ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => {
+                self.position += 1;
+                let mut text = String::new();
+                loop {
+                    text.push_str(self.take_while(|c| c != '"'));
+                    if self.peek().is_none() {
+                        return Err(self.error("unterminated string"));
+                    }
+                    self.position += 1;
+                    // a doubled quote is an escaped quote
+                    if self.peek() == Some('"') {
+                        text.push('"');
+                        self.position += 1;
+                    } else {
+                        return Ok(WktValue::Text(text));
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let start = self.position;
+                let word = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                self.skip_whitespace();
+                if matches!(self.peek(), Some('[') | Some('(')) {
+                    self.position = start;
+                    Ok(WktValue::Node(self.node()?))
+                } else {
+                    Ok(WktValue::Bare(word.to_string()))
+                }
+            }
+            Some(_) => {
+                let value = self.take_while(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+                if value.is_empty() {
+                    return Err(self.error("expected a value"));
+                }
+                Ok(WktValue::Bare(value.to_string()))
+            }
+            None => Err(self.error("unexpected end")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kma_grid::{KmaGrid, OutOfRange};
+    use crate::projection::{LambertConformalConic, Projection};
+
+    fn assert_same_grids(parsed: &GridSpec, spec: &GridSpec, tolerance: f64) {
+        assert_eq!(parsed.nx, spec.nx);
+        assert_eq!(parsed.ny, spec.ny);
+        assert!((parsed.reference_x - spec.reference_x).abs() < tolerance);
+        assert!((parsed.reference_y - spec.reference_y).abs() < tolerance);
+        for (x, y) in [(1, 1), (43, 136), (60, 127), (spec.nx, spec.ny)] {
+            let (longitude, latitude) = KmaGrid::new(x, y).to_gcs_in(spec).unwrap();
+            let grid = KmaGrid::try_from_gcs_in(parsed, longitude, latitude, OutOfRange::Error);
+            assert_eq!(grid, Ok(KmaGrid::new(x, y)));
+        }
+    }
+
+    #[test]
+    fn proj_string_round_trip() {
+        for spec in [
+            GridSpec::DFS_5KM,
+            GridSpec::LDAPS_1_5KM,
+            GridSpec {
+                ellipsoid: Ellipsoid::GRS80,
+                grid_length: 1.0,
+                ..GridSpec::DFS_5KM
+            },
+        ] {
+            let parsed = GridSpec::from_proj_string(
+                &spec.to_proj_string(),
+                spec.grid_length,
+                spec.nx,
+                spec.ny,
+            )
+            .unwrap();
+            assert_same_grids(&parsed, &spec, 1e-9);
+        }
+    }
+
+    #[test]
+    fn wkt_round_trip() {
+        for spec in [GridSpec::DFS_5KM, GridSpec::LDAPS_1_5KM] {
+            let parsed =
+                GridSpec::from_wkt(&spec.to_wkt(), spec.grid_length, spec.nx, spec.ny).unwrap();
+            assert_same_grids(&parsed, &spec, 1e-9);
+        }
+    }
+
+    #[test]
+    fn geo_transform_round_trip() {
+        // pixel centres of the geotransform through the parsed definitions land
+        // on the grid centres of the spec that emitted them
+        for spec in [GridSpec::DFS_5KM, GridSpec::LDAPS_1_5KM] {
+            let [x_origin, dx, _, y_origin, _, dy] = spec.geo_transform();
+            let (length, nx, ny) = (spec.grid_length, spec.nx, spec.ny);
+            for parsed in [
+                GridSpec::from_proj_string(&spec.to_proj_string(), length, nx, ny).unwrap(),
+                GridSpec::from_wkt(&spec.to_wkt(), length, nx, ny).unwrap(),
+            ] {
+                let lcc = LambertConformalConic::new(
+                    parsed.ellipsoid,
+                    parsed.standard_parallel1,
+                    parsed.standard_parallel2,
+                    parsed.reference_longitude,
+                    parsed.reference_latitude,
+                );
+                for (column, row) in [(0, 0), (42, 117), (nx - 1, ny - 1)] {
+                    let easting = x_origin + (column as f64 + 0.5) * dx;
+                    let northing = y_origin + (row as f64 + 0.5) * dy;
+                    let (longitude, latitude) = lcc.inverse(
+                        (easting - parsed.false_easting()) / 1000.0,
+                        (northing - parsed.false_northing()) / 1000.0,
+                    );
+
+                    let grid = KmaGrid::new(column + 1, ny - row);
+                    let (lon, lat) = grid.to_gcs_in(&spec).unwrap();
+                    assert!((lon - longitude).abs() < 1e-9);
+                    assert!((lat - latitude).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wkt1() {
+        let wkt = r#"PROJCS["Korea LCC",
+            GEOGCS["WGS 84",
+                DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563]],
+                PRIMEM["Greenwich",0],
+                UNIT["degree",0.0174532925199433]],
+            PROJECTION["Lambert_Conformal_Conic_2SP"],
+            PARAMETER["standard_parallel_1",30],
+            PARAMETER["standard_parallel_2",60],
+            PARAMETER["latitude_of_origin",38],
+            PARAMETER["central_meridian",126],
+            PARAMETER["false_easting",210],
+            PARAMETER["false_northing",675],
+            UNIT["kilometre",1000]]"#;
+        let spec = GridSpec::from_wkt(wkt, 5.0, 149, 253).unwrap();
+        assert_eq!(
+            spec,
+            GridSpec {
+                ellipsoid: Ellipsoid::WGS84,
+                ..GridSpec::DFS_5KM
+            }
+        );
+    }
+
+    #[test]
+    fn wide_whitespace() {
+        // whitespace of more than a byte, as pasted from documents
+        let wkt = GridSpec::DFS_5KM
+            .to_wkt()
+            .replace(",PARAMETER", ",\u{a0}PARAMETER")
+            .replace(",ID[", ",\u{3000}ID[\u{3000}");
+        assert!(wkt.contains('\u{a0}') && wkt.contains('\u{3000}'));
+        let parsed = GridSpec::from_wkt(&wkt, 5.0, 149, 253).unwrap();
+        assert_same_grids(&parsed, &GridSpec::DFS_5KM, 1e-9);
+        for space in ['\u{a0}', '\u{3000}'] {
+            let wkt = format!(r#"PROJCS["x",{}PROJECTION["Transverse_Mercator"]]"#, space);
+            assert!(matches!(
+                GridSpec::from_wkt(&wkt, 5.0, 1, 1),
+                Err(ParseError::UnsupportedProjection(_))
+            ));
+            let wkt = format!(r#"PROJCS["x",{}"#, space);
+            assert!(matches!(
+                GridSpec::from_wkt(&wkt, 5.0, 1, 1),
+                Err(ParseError::Syntax(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn partner_grid() {
+        // a grid anchored by its first grid point rather than the false origin
+        let spec = GridSpec::from_proj_string(
+            "+proj=lcc +lat_1=30 +lat_2=60 +lat_0=38 +lon_0=126 +a=6371229 +b=6371229",
+            1.5,
+            602,
+            781,
+        )
+        .unwrap()
+        .with_grid_at(1.0, 1.0, 121.834, 32.2569)
+        .unwrap();
+        // the published first grid point is rounded to 1e-4 degree
+        assert_same_grids(&spec, &GridSpec::LDAPS_1_5KM, 1e-2);
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(
+            GridSpec::from_proj_string("+proj=merc +lon_0=126", 5.0, 149, 253),
+            Err(ParseError::UnsupportedProjection("merc".to_string()))
+        );
+        assert_eq!(
+            GridSpec::from_proj_string("+proj=lcc +lat_1=30 +k_0=0.9996", 5.0, 149, 253),
+            Err(ParseError::UnsupportedParameter("k_0=0.9996".to_string()))
+        );
+        assert_eq!(
+            GridSpec::from_proj_string("+proj=lcc +lat_0=38", 5.0, 149, 253),
+            Err(ParseError::MissingParameter("standard parallel"))
+        );
+        assert_eq!(
+            GridSpec::from_proj_string("+proj=lcc +lat_1=north", 5.0, 149, 253),
+            Err(ParseError::InvalidValue {
+                name: "lat_1".to_string(),
+                value: "north".to_string()
+            })
+        );
+        assert!(matches!(
+            GridSpec::from_wkt(
+                r#"PROJCS["x",PROJECTION["Transverse_Mercator"]]"#,
+                5.0,
+                1,
+                1
+            ),
+            Err(ParseError::UnsupportedProjection(_))
+        ));
+        assert!(matches!(
+            GridSpec::from_wkt(r#"PROJCS["x",PROJECTION["Lambert"#, 5.0, 1, 1),
+            Err(ParseError::Syntax(_))
+        ));
+    }
+}
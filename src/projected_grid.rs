@@ -0,0 +1,142 @@
+use crate::error::GridError;
+use crate::kma_grid::{KmaGrid, OutOfRange};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::projection::{LatLon, Projection};
+
+// A regular grid on the plane of a projection. Grid coordinates are 1 ~ nx and
+// 1 ~ ny at grid centres, with (reference_x, reference_y) at the plane origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectedGrid<P> {
+    pub projection: P,
+    pub dx: f64,          // grid length along x in the units of the projection
+    pub dy: f64,          // grid length along y in the units of the projection
+    pub reference_x: f64, // x coordinate of the plane origin
+    pub reference_y: f64, // y coordinate of the plane origin
+    pub nx: i32,          // number of grids along the x axis
+    pub ny: i32,          // number of grids along the y axis
+}
+
+impl ProjectedGrid<LatLon> {
+    // N1280 grid of the global data assimilation and prediction system (GDAPS),
+    // whose first grid point is half a grid off (0, -90)
+    pub const GDAPS_N1280: ProjectedGrid<LatLon> = ProjectedGrid {
+        projection: LatLon {
+            central_longitude: 180.0,
+        },
+        dx: 0.140625,
+        dy: 0.09375,
+        reference_x: 0.5,
+        reference_y: 960.5,
+        nx: 2560,
+        ny: 1920,
+    };
+}
+
+impl<P: Projection> ProjectedGrid<P> {
+    // whether the grid lies in 1 ~ nx, 1 ~ ny
+    pub fn contains(&self, grid: &KmaGrid) -> bool {
+        (1..=self.nx).contains(&grid.x()) && (1..=self.ny).contains(&grid.y())
+    }
+
+    // unrounded grid coordinates of a point, where integers are grid centres.
+    // Over the KMA grids grid_to_gcs undoes it to within 1e-10 degrees (about
+    // 10 um on the ground) and it undoes grid_to_gcs to within 1e-9 grid lengths,
+    // as tested in tests/properties.rs.
+    pub fn gcs_to_grid(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        let (x, y) = self.projection.forward(longitude, latitude)?;
+        Ok((
+            x / self.dx + self.reference_x,
+            y / self.dy + self.reference_y,
+        ))
+    }
+
+    // longitude and latitude of unrounded grid coordinates
+    pub fn grid_to_gcs(&self, x: f64, y: f64) -> (f64, f64) {
+        self.projection.inverse(
+            (x - self.reference_x) * self.dx,
+            (y - self.reference_y) * self.dy,
+        )
+    }
+
+    pub fn from_gcs(&self, longitude: f64, latitude: f64) -> Result<KmaGrid, GridError> {
+        self.from_gcs_with(longitude, latitude, OutOfRange::Error)
+    }
+
+    pub fn from_gcs_with(
+        &self,
+        longitude: f64,
+        latitude: f64,
+        out_of_range: OutOfRange,
+    ) -> Result<KmaGrid, GridError> {
+        let (x, y) = self.gcs_to_grid(longitude, latitude)?;
+        let (x, y) = (x.round() as i32, y.round() as i32);
+
+        let grid = KmaGrid::new(x, y);
+        match out_of_range {
+            _ if self.contains(&grid) => Ok(grid),
+            OutOfRange::Error => Err(GridError::OutOfDomain { x, y }),
+            OutOfRange::Clamp => Ok(KmaGrid::new(x.clamp(1, self.nx), y.clamp(1, self.ny))),
+            OutOfRange::Unbounded => Ok(grid),
+        }
+    }
+
+    pub(crate) fn check_contains(&self, grid: KmaGrid) -> Result<(), GridError> {
+        if !self.contains(&grid) {
+            return Err(GridError::OutOfDomain {
+                x: grid.x(),
+                y: grid.y(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn to_gcs(&self, grid: KmaGrid) -> Result<(f64, f64), GridError> {
+        self.check_contains(grid)?;
+        Ok(self.grid_to_gcs(grid.x() as f64, grid.y() as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+    use crate::projection::PolarStereographic;
+
+    #[test]
+    fn gdaps_lat_lon() {
+        let grid = ProjectedGrid::GDAPS_N1280;
+        assert_eq!(grid.from_gcs(0.0703125, -89.953125), Ok(KmaGrid::new(1, 1)));
+        assert_eq!(
+            grid.from_gcs(-0.0703125, 89.953125),
+            Ok(KmaGrid::new(2560, 1920))
+        );
+
+        let seoul = grid.from_gcs(126.978, 37.5665).unwrap();
+        let (longitude, latitude) = grid.to_gcs(seoul).unwrap();
+        assert!((longitude - 126.978).abs() <= 0.140625 / 2.0);
+        assert!((latitude - 37.5665).abs() <= 0.09375 / 2.0);
+    }
+
+    #[test]
+    fn polar_stereographic() {
+        let grid = ProjectedGrid {
+            projection: PolarStereographic {
+                ellipsoid: Ellipsoid::KMA_SPHERE,
+                central_longitude: 126.0,
+                latitude_of_true_scale: 60.0,
+            },
+            dx: 10.0,
+            dy: 10.0,
+            reference_x: 201.0,
+            reference_y: 701.0,
+            nx: 401,
+            ny: 701,
+        };
+        // the north pole is at the top centre
+        assert_eq!(grid.from_gcs(0.0, 90.0), Ok(KmaGrid::new(201, 701)));
+        let cell = grid.from_gcs(126.978, 37.5665).unwrap();
+        let (x, y) = grid.gcs_to_grid(126.978, 37.5665).unwrap();
+        assert_eq!(cell, KmaGrid::new(x.round() as i32, y.round() as i32));
+    }
+}
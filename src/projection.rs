@@ -0,0 +1,119 @@
+use core::f64;
+
+use crate::error::GridError;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+mod lambert_conformal_conic;
+mod lat_lon;
+mod mercator;
+mod polar_stereographic;
+mod transverse_mercator;
+
+pub use self::lambert_conformal_conic::LambertConformalConic;
+pub use self::lat_lon::LatLon;
+pub use self::mercator::Mercator;
+pub use self::polar_stereographic::PolarStereographic;
+pub use self::transverse_mercator::TransverseMercator;
+
+pub(crate) const DEGREE_TO_RADIAN: f64 = f64::consts::PI / 180.0;
+pub(crate) const RADIAN_TO_DEGREE: f64 = 180.0 / f64::consts::PI;
+
+// Maps longitude and latitude in degrees onto a projected plane and back.
+// Plane coordinates are in km, except for TransverseMercator which keeps
+// the metres of its EPSG definitions and LatLon which stays in degrees.
+pub trait Projection {
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError>;
+
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64);
+
+    // forward with NaN for a point that cannot be projected, for passes over
+    // many points that should not branch on a Result
+    fn forward_or_nan(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        self.forward(longitude, latitude)
+            .unwrap_or((f64::NAN, f64::NAN))
+    }
+}
+
+impl<P: Projection + ?Sized> Projection for &P {
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        (**self).forward(longitude, latitude)
+    }
+
+    fn forward_or_nan(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        (**self).forward_or_nan(longitude, latitude)
+    }
+
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        (**self).inverse(x, y)
+    }
+}
+
+pub(crate) fn check_gcs(longitude: f64, latitude: f64) -> Result<(), GridError> {
+    if !longitude.is_finite() || !latitude.is_finite() {
+        return Err(GridError::NonFinite {
+            longitude,
+            latitude,
+        });
+    }
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(GridError::InvalidLatitude(latitude));
+    }
+    Ok(())
+}
+
+// wraps an angle in radian into -pi ~ pi
+pub(crate) fn wrap_angle(angle: f64) -> f64 {
+    // a single turn off is taken away as it is, any more by the remainder
+    let angle = if angle.abs() > 3.0 * f64::consts::PI {
+        angle.rem_euclid(2.0 * f64::consts::PI)
+    } else {
+        angle
+    };
+    if angle > f64::consts::PI {
+        angle - 2.0 * f64::consts::PI
+    } else if angle < -f64::consts::PI {
+        angle + 2.0 * f64::consts::PI
+    } else {
+        angle
+    }
+}
+
+// cos(phi) / sqrt(1 - e^2 sin^2(phi)), just cos(phi) on a sphere
+pub(crate) fn m(latitude: f64, e: f64) -> f64 {
+    if e == 0.0 {
+        latitude.cos()
+    } else {
+        latitude.cos() / (1.0 - (e * latitude.sin()).powi(2)).sqrt()
+    }
+}
+
+// tan(pi/4 + phi/2) corrected for the ellipsoid, the reciprocal of Snyder's t
+pub(crate) fn q(latitude: f64, e: f64) -> f64 {
+    let tan = (f64::consts::PI * 0.25 + 0.5 * latitude).tan();
+    if e == 0.0 {
+        tan
+    } else {
+        let es = e * latitude.sin();
+        tan * ((1.0 - es) / (1.0 + es)).powf(0.5 * e)
+    }
+}
+
+// the latitude whose q is the given value, Snyder (7-9)
+pub(crate) fn latitude_of_q(q: f64, e: f64) -> f64 {
+    let mut latitude = 2.0 * q.atan() - f64::consts::PI * 0.5;
+    if e != 0.0 {
+        // the conformal latitude converges to the geodetic one in a few iterations
+        for _ in 0..15 {
+            let es = e * latitude.sin();
+            let next =
+                2.0 * (q * ((1.0 + es) / (1.0 - es)).powf(0.5 * e)).atan() - f64::consts::PI * 0.5;
+            let delta = (next - latitude).abs();
+            latitude = next;
+            if delta < 1e-12 {
+                break;
+            }
+        }
+    }
+    latitude
+}
@@ -0,0 +1,231 @@
+use core::f64;
+
+use super::{latitude_of_q, m, q, wrap_angle, Projection, DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+use crate::ellipsoid::Ellipsoid;
+use crate::error::GridError;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LccConstants {
+    pub(crate) n: f64,
+    pub(crate) f: f64,
+    pub(crate) rho_zero: f64,
+    pub(crate) earth_radius: f64, // km
+    pub(crate) e: f64,            // eccentricity of the ellipsoid
+}
+
+// https://en.wikipedia.org/wiki/Lambert_conformal_conic_projection#Transformation
+// Lambert conformal conic projection, with the origin at the reference point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LambertConformalConic {
+    constants: LccConstants,
+    reference_longitude: f64, // radian
+}
+
+impl LambertConformalConic {
+    // all angles in degree
+    pub fn new(
+        ellipsoid: Ellipsoid,
+        standard_parallel1: f64,
+        standard_parallel2: f64,
+        reference_longitude: f64,
+        reference_latitude: f64,
+    ) -> LambertConformalConic {
+        let standard_parallel1 = standard_parallel1 * DEGREE_TO_RADIAN;
+        let standard_parallel2 = standard_parallel2 * DEGREE_TO_RADIAN;
+        let reference_latitude = reference_latitude * DEGREE_TO_RADIAN;
+        let earth_radius = ellipsoid.semi_major_axis;
+        let e = ellipsoid.eccentricity();
+
+        // a tangent cone when both standard parallels are the same
+        let n = if standard_parallel1 == standard_parallel2 {
+            standard_parallel1.sin()
+        } else {
+            (m(standard_parallel1, e) / m(standard_parallel2, e)).ln()
+                / (q(standard_parallel2, e) / q(standard_parallel1, e)).ln()
+        };
+
+        let f = q(standard_parallel1, e).powf(n) * m(standard_parallel1, e) / n;
+
+        let rho_zero = earth_radius * f / q(reference_latitude, e).powf(n);
+        LambertConformalConic {
+            constants: LccConstants {
+                n,
+                f,
+                rho_zero,
+                earth_radius,
+                e,
+            },
+            reference_longitude: reference_longitude * DEGREE_TO_RADIAN,
+        }
+    }
+
+    // n, the ratio of the angle between meridians on the plane to their longitude difference
+    pub fn cone_constant(&self) -> f64 {
+        self.constants.n
+    }
+
+    // ratio of a length on the plane to the length on the ground, 1 on the standard parallels
+    pub fn scale_factor(&self, latitude: f64) -> f64 {
+        let LccConstants { n, f, e, .. } = self.constants;
+        let latitude = latitude * DEGREE_TO_RADIAN;
+        n * f / (q(latitude, e).powf(n) * m(latitude, e))
+    }
+
+    // angle in degree from true north clockwise to grid north, positive east
+    // of the reference longitude
+    pub fn convergence(&self, longitude: f64) -> f64 {
+        let theta = wrap_angle(longitude * DEGREE_TO_RADIAN - self.reference_longitude);
+        self.constants.n * theta * RADIAN_TO_DEGREE
+    }
+
+    // plane coordinates of a point, without checking it
+    fn project(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        let LccConstants {
+            n,
+            f,
+            rho_zero,
+            earth_radius,
+            e,
+        } = self.constants;
+        let rho = earth_radius * f / q(latitude * DEGREE_TO_RADIAN, e).powf(n);
+        let theta = wrap_angle(longitude * DEGREE_TO_RADIAN - self.reference_longitude);
+
+        let x = rho * (theta * n).sin();
+        let y = rho_zero - rho * (theta * n).cos();
+
+        (x, y)
+    }
+}
+
+impl Projection for LambertConformalConic {
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        super::check_gcs(longitude, latitude)?;
+        // the south pole is projected to infinity
+        if latitude == -90.0 {
+            return Err(GridError::InvalidLatitude(latitude));
+        }
+        Ok(self.project(longitude, latitude))
+    }
+
+    fn forward_or_nan(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        // NaN and infinite longitudes come out as NaN by themselves
+        let latitude = if latitude > -90.0 && latitude <= 90.0 {
+            latitude
+        } else {
+            f64::NAN
+        };
+        self.project(longitude, latitude)
+    }
+
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let LccConstants {
+            n,
+            f,
+            rho_zero,
+            earth_radius,
+            e,
+        } = self.constants;
+        // a cone opening to the south has both axes negated (Snyder 15-10)
+        let (xn, yn) = if n < 0.0 {
+            (-x, y - rho_zero)
+        } else {
+            (x, rho_zero - y)
+        };
+
+        let ra = {
+            let ra = (xn.powi(2) + yn.powi(2)).sqrt();
+            if n < 0.0 {
+                -ra
+            } else {
+                ra
+            }
+        };
+
+        let latitude_radian = latitude_of_q((earth_radius * f / ra).powf(1.0 / n), e);
+
+        // theta is measured from the y axis, hence atan2(x, y)
+        let theta = if xn.abs() == 0.0 {
+            0.0
+        } else if yn.abs() == 0.0 {
+            f64::consts::PI * 0.5 * xn.signum()
+        } else {
+            xn.atan2(yn)
+        };
+
+        let longitude_radian = theta / n + self.reference_longitude;
+
+        (
+            longitude_radian * RADIAN_TO_DEGREE,
+            latitude_radian * RADIAN_TO_DEGREE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tangent_cone() {
+        let lcc = LambertConformalConic::new(Ellipsoid::GRS80, 38.0, 38.0, 126.0, 38.0);
+        assert!((lcc.cone_constant() - (38.0 * DEGREE_TO_RADIAN).sin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn origin() {
+        let lcc = LambertConformalConic::new(Ellipsoid::GRS80, 30.0, 60.0, 126.0, 38.0);
+        assert_eq!(lcc.forward(126.0, 38.0), Ok((0.0, 0.0)));
+        let (longitude, latitude) = lcc.inverse(0.0, 0.0);
+        assert!((longitude - 126.0).abs() < 1e-12);
+        assert!((latitude - 38.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn scale_factor() {
+        for ellipsoid in [Ellipsoid::KMA_SPHERE, Ellipsoid::GRS80] {
+            let lcc = LambertConformalConic::new(ellipsoid, 30.0, 60.0, 126.0, 38.0);
+            assert!((lcc.scale_factor(30.0) - 1.0).abs() < 1e-12);
+            assert!((lcc.scale_factor(60.0) - 1.0).abs() < 1e-12);
+            assert!(lcc.scale_factor(45.0) < 1.0);
+            assert!(lcc.scale_factor(20.0) > 1.0);
+
+            // the ratio of a short meridian arc on the plane to the one on the ground
+            let (_, y0) = lcc.forward(126.0, 37.999).unwrap();
+            let (_, y1) = lcc.forward(126.0, 38.001).unwrap();
+            let lat = 38.0 * DEGREE_TO_RADIAN;
+            let e2 = ellipsoid.eccentricity().powi(2);
+            let meridian_radius =
+                ellipsoid.semi_major_axis * (1.0 - e2) / (1.0 - e2 * lat.sin().powi(2)).powf(1.5);
+            let arc = meridian_radius * 0.002 * DEGREE_TO_RADIAN;
+            assert!(((y1 - y0) / arc - lcc.scale_factor(38.0)).abs() < 1e-8);
+
+            assert_eq!(lcc.convergence(126.0), 0.0);
+            assert!((lcc.convergence(131.0) - 5.0 * lcc.cone_constant()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn southern_cone() {
+        for ellipsoid in [Ellipsoid::KMA_SPHERE, Ellipsoid::GRS80] {
+            let lcc = LambertConformalConic::new(ellipsoid, -30.0, -60.0, 145.0, -38.0);
+            assert!(lcc.cone_constant() < 0.0);
+            for (longitude, latitude) in [
+                (147.0, -37.0),
+                (143.0, -40.0),
+                (145.0, -20.0),
+                (160.0, -55.0),
+            ] {
+                let (x, y) = lcc.forward(longitude, latitude).unwrap();
+                let (lon, lat) = lcc.inverse(x, y);
+                assert!((lon - longitude).abs() < 1e-9, "{:?}", (longitude, lon));
+                assert!((lat - latitude).abs() < 1e-9, "{:?}", (latitude, lat));
+            }
+            // east is still to the right and south down
+            let (x, _) = lcc.forward(147.0, -38.0).unwrap();
+            let (_, y) = lcc.forward(145.0, -40.0).unwrap();
+            assert!(x > 0.0 && y < 0.0);
+        }
+    }
+}
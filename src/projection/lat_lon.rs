@@ -0,0 +1,36 @@
+use super::{wrap_angle, Projection, DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+use crate::error::GridError;
+
+// Regular longitude and latitude, where the plane is in degree.
+// Longitudes are wrapped into central_longitude - 180 ~ central_longitude + 180.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LatLon {
+    pub central_longitude: f64, // degree
+}
+
+impl Projection for LatLon {
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        super::check_gcs(longitude, latitude)?;
+        let lambda = wrap_angle((longitude - self.central_longitude) * DEGREE_TO_RADIAN);
+        Ok((self.central_longitude + lambda * RADIAN_TO_DEGREE, latitude))
+    }
+
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        (x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_longitude() {
+        let lat_lon = LatLon {
+            central_longitude: 180.0,
+        };
+        let (longitude, latitude) = lat_lon.forward(-10.0, 37.5).unwrap();
+        assert!((longitude - 350.0).abs() < 1e-9);
+        assert_eq!(latitude, 37.5);
+    }
+}
@@ -0,0 +1,74 @@
+use super::{latitude_of_q, m, q, wrap_angle, Projection, DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+use crate::ellipsoid::Ellipsoid;
+use crate::error::GridError;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+// Normal aspect Mercator projection, Snyder (7-6) ~ (7-9)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mercator {
+    pub ellipsoid: Ellipsoid,
+    pub central_longitude: f64,      // degree
+    pub latitude_of_true_scale: f64, // degree
+}
+
+impl Mercator {
+    // radius of the equator scaled to the true scale latitude, km
+    fn scaled_radius(&self) -> f64 {
+        let e = self.ellipsoid.eccentricity();
+        self.ellipsoid.semi_major_axis * m(self.latitude_of_true_scale * DEGREE_TO_RADIAN, e)
+    }
+}
+
+impl Projection for Mercator {
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        super::check_gcs(longitude, latitude)?;
+        // both poles are projected to infinity
+        if latitude.abs() == 90.0 {
+            return Err(GridError::InvalidLatitude(latitude));
+        }
+
+        let e = self.ellipsoid.eccentricity();
+        let radius = self.scaled_radius();
+        let lambda = wrap_angle((longitude - self.central_longitude) * DEGREE_TO_RADIAN);
+
+        Ok((
+            radius * lambda,
+            radius * q(latitude * DEGREE_TO_RADIAN, e).ln(),
+        ))
+    }
+
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let e = self.ellipsoid.eccentricity();
+        let radius = self.scaled_radius();
+
+        (
+            self.central_longitude + x / radius * RADIAN_TO_DEGREE,
+            latitude_of_q((y / radius).exp(), e) * RADIAN_TO_DEGREE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mercator = Mercator {
+            ellipsoid: Ellipsoid::WGS84,
+            central_longitude: 126.0,
+            latitude_of_true_scale: 0.0,
+        };
+        let (x, y) = mercator.forward(126.0, 0.0).unwrap();
+        assert!(x.abs() < 1e-9 && y.abs() < 1e-9);
+        // one degree of longitude on the equator
+        let (x, _) = mercator.forward(127.0, 0.0).unwrap();
+        assert!((x - 111.319491).abs() < 1e-6);
+
+        let (x, y) = mercator.forward(129.5, 35.1).unwrap();
+        let (longitude, latitude) = mercator.inverse(x, y);
+        assert!((longitude - 129.5).abs() < 1e-9);
+        assert!((latitude - 35.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,116 @@
+use super::{latitude_of_q, m, q, wrap_angle, Projection, DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+use crate::ellipsoid::Ellipsoid;
+use crate::error::GridError;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+// Polar stereographic projection, Snyder (21-33) ~ (21-40).
+// The pole is the north pole when the latitude of true scale is positive
+// and the south pole when negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolarStereographic {
+    pub ellipsoid: Ellipsoid,
+    pub central_longitude: f64,      // degree, pointing down from the pole
+    pub latitude_of_true_scale: f64, // degree
+}
+
+impl PolarStereographic {
+    fn sign(&self) -> f64 {
+        if self.latitude_of_true_scale < 0.0 {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    // rho / t, the distance from the pole per Snyder's t
+    fn rho_per_t(&self) -> f64 {
+        let a = self.ellipsoid.semi_major_axis;
+        let e = self.ellipsoid.eccentricity();
+        let latitude_of_true_scale = self.latitude_of_true_scale.abs() * DEGREE_TO_RADIAN;
+        if self.latitude_of_true_scale.abs() == 90.0 {
+            2.0 * a / ((1.0 + e).powf(1.0 + e) * (1.0 - e).powf(1.0 - e)).sqrt()
+        } else {
+            a * m(latitude_of_true_scale, e) * q(latitude_of_true_scale, e)
+        }
+    }
+}
+
+impl Projection for PolarStereographic {
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        super::check_gcs(longitude, latitude)?;
+        let sign = self.sign();
+        // the opposite pole is projected to infinity
+        if latitude * sign == -90.0 {
+            return Err(GridError::InvalidLatitude(latitude));
+        }
+
+        let e = self.ellipsoid.eccentricity();
+        // south polar aspects mirror the north polar one
+        let t = 1.0 / q(sign * latitude * DEGREE_TO_RADIAN, e);
+        let rho = self.rho_per_t() * t;
+        let lambda = sign * wrap_angle((longitude - self.central_longitude) * DEGREE_TO_RADIAN);
+
+        Ok((sign * rho * lambda.sin(), -sign * rho * lambda.cos()))
+    }
+
+    fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let sign = self.sign();
+        let e = self.ellipsoid.eccentricity();
+        let (x, y) = (sign * x, sign * y);
+
+        let rho = (x.powi(2) + y.powi(2)).sqrt();
+        let t = rho / self.rho_per_t();
+        let latitude = latitude_of_q(1.0 / t, e);
+        let lambda = if rho == 0.0 { 0.0 } else { x.atan2(-y) };
+
+        (
+            self.central_longitude + sign * lambda * RADIAN_TO_DEGREE,
+            sign * latitude * RADIAN_TO_DEGREE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for latitude_of_true_scale in [60.0, 90.0, -71.0] {
+            let stereographic = PolarStereographic {
+                ellipsoid: Ellipsoid::WGS84,
+                central_longitude: 126.0,
+                latitude_of_true_scale,
+            };
+            let sign = latitude_of_true_scale.signum();
+            let (x, y) = stereographic.forward(0.0, sign * 90.0).unwrap();
+            assert!(x.abs() < 1e-9 && y.abs() < 1e-9);
+
+            for (longitude, latitude) in [(126.0, 38.0), (100.0, 50.0), (-170.0, 10.0)] {
+                let latitude = sign * latitude;
+                let (x, y) = stereographic.forward(longitude, latitude).unwrap();
+                let (lon, lat) = stereographic.inverse(x, y);
+                assert!(
+                    (lon - longitude)
+                        .rem_euclid(360.0)
+                        .min((longitude - lon).rem_euclid(360.0))
+                        < 1e-9
+                );
+                assert!((lat - latitude).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn central_longitude_points_down() {
+        let stereographic = PolarStereographic {
+            ellipsoid: Ellipsoid::KMA_SPHERE,
+            central_longitude: 126.0,
+            latitude_of_true_scale: 60.0,
+        };
+        let (x, y) = stereographic.forward(126.0, 38.0).unwrap();
+        assert!(x.abs() < 1e-9);
+        assert!(y < 0.0);
+    }
+}
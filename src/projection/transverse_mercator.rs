@@ -0,0 +1,173 @@
+use super::{Projection, DEGREE_TO_RADIAN, RADIAN_TO_DEGREE};
+use crate::ellipsoid::Ellipsoid;
+use crate::error::GridError;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+// Parameters of a transverse Mercator projection with coordinates in metres
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransverseMercator {
+    pub ellipsoid: Ellipsoid,
+    pub central_meridian: f64,   // degree
+    pub latitude_of_origin: f64, // degree
+    pub scale_factor: f64,
+    pub false_easting: f64,  // m
+    pub false_northing: f64, // m
+}
+
+impl TransverseMercator {
+    // Korea 2000 / Unified CS (EPSG:5179), used by the road name address database
+    pub const UTM_K: TransverseMercator = TransverseMercator {
+        ellipsoid: Ellipsoid::GRS80,
+        central_meridian: 127.5,
+        latitude_of_origin: 38.0,
+        scale_factor: 0.9996,
+        false_easting: 1_000_000.0,
+        false_northing: 2_000_000.0,
+    };
+
+    // Korea 2000 / West Belt 2010 (EPSG:5185)
+    pub const WEST_BELT: TransverseMercator = TransverseMercator::korea_2000_belt(125.0);
+    // Korea 2000 / Central Belt 2010 (EPSG:5186)
+    pub const CENTRAL_BELT: TransverseMercator = TransverseMercator::korea_2000_belt(127.0);
+    // Korea 2000 / East Belt 2010 (EPSG:5187)
+    pub const EAST_BELT: TransverseMercator = TransverseMercator::korea_2000_belt(129.0);
+    // Korea 2000 / East Sea Belt 2010 (EPSG:5188)
+    pub const EAST_SEA_BELT: TransverseMercator = TransverseMercator::korea_2000_belt(131.0);
+
+    const fn korea_2000_belt(central_meridian: f64) -> TransverseMercator {
+        TransverseMercator {
+            ellipsoid: Ellipsoid::GRS80,
+            central_meridian,
+            latitude_of_origin: 38.0,
+            scale_factor: 1.0,
+            false_easting: 200_000.0,
+            false_northing: 600_000.0,
+        }
+    }
+}
+
+// Snyder, Map Projections: A Working Manual, (8-9) ~ (8-25)
+impl Projection for TransverseMercator {
+    // easting and northing in metres of a point
+    fn forward(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        super::check_gcs(longitude, latitude)?;
+
+        let a = self.ellipsoid.semi_major_axis * 1000.0;
+        let e2 = self.ellipsoid.eccentricity().powi(2);
+        let ep2 = e2 / (1.0 - e2);
+        let k0 = self.scale_factor;
+        let phi = latitude * DEGREE_TO_RADIAN;
+
+        let n = a / (1.0 - e2 * phi.sin().powi(2)).sqrt();
+        let t = phi.tan().powi(2);
+        let c = ep2 * phi.cos().powi(2);
+        let a_ = (longitude - self.central_meridian) * DEGREE_TO_RADIAN * phi.cos();
+
+        let x = k0
+            * n
+            * (a_
+                + (1.0 - t + c) * a_.powi(3) / 6.0
+                + (5.0 - 18.0 * t + t.powi(2) + 72.0 * c - 58.0 * ep2) * a_.powi(5) / 120.0);
+        let y = k0
+            * (meridian_arc(a, e2, phi)
+                - meridian_arc(a, e2, self.latitude_of_origin * DEGREE_TO_RADIAN)
+                + n * phi.tan()
+                    * (a_.powi(2) / 2.0
+                        + (5.0 - t + 9.0 * c + 4.0 * c.powi(2)) * a_.powi(4) / 24.0
+                        + (61.0 - 58.0 * t + t.powi(2) + 600.0 * c - 330.0 * ep2) * a_.powi(6)
+                            / 720.0));
+
+        Ok((x + self.false_easting, y + self.false_northing))
+    }
+
+    // longitude and latitude of a point in metres
+    fn inverse(&self, easting: f64, northing: f64) -> (f64, f64) {
+        let a = self.ellipsoid.semi_major_axis * 1000.0;
+        let e2 = self.ellipsoid.eccentricity().powi(2);
+        let ep2 = e2 / (1.0 - e2);
+        let k0 = self.scale_factor;
+        let x = easting - self.false_easting;
+        let y = northing - self.false_northing;
+
+        let m = meridian_arc(a, e2, self.latitude_of_origin * DEGREE_TO_RADIAN) + y / k0;
+        let mu = m / (a * (1.0 - e2 / 4.0 - 3.0 * e2.powi(2) / 64.0 - 5.0 * e2.powi(3) / 256.0));
+        let e1 = (1.0 - (1.0 - e2).sqrt()) / (1.0 + (1.0 - e2).sqrt());
+        // footpoint latitude
+        let phi1 = mu
+            + (3.0 * e1 / 2.0 - 27.0 * e1.powi(3) / 32.0) * (2.0 * mu).sin()
+            + (21.0 * e1.powi(2) / 16.0 - 55.0 * e1.powi(4) / 32.0) * (4.0 * mu).sin()
+            + (151.0 * e1.powi(3) / 96.0) * (6.0 * mu).sin()
+            + (1097.0 * e1.powi(4) / 512.0) * (8.0 * mu).sin();
+
+        let c1 = ep2 * phi1.cos().powi(2);
+        let t1 = phi1.tan().powi(2);
+        let n1 = a / (1.0 - e2 * phi1.sin().powi(2)).sqrt();
+        let r1 = a * (1.0 - e2) / (1.0 - e2 * phi1.sin().powi(2)).powf(1.5);
+        let d = x / (n1 * k0);
+
+        let phi = phi1
+            - (n1 * phi1.tan() / r1)
+                * (d.powi(2) / 2.0
+                    - (5.0 + 3.0 * t1 + 10.0 * c1 - 4.0 * c1.powi(2) - 9.0 * ep2) * d.powi(4)
+                        / 24.0
+                    + (61.0 + 90.0 * t1 + 298.0 * c1 + 45.0 * t1.powi(2)
+                        - 252.0 * ep2
+                        - 3.0 * c1.powi(2))
+                        * d.powi(6)
+                        / 720.0);
+        let lambda = (d - (1.0 + 2.0 * t1 + c1) * d.powi(3) / 6.0
+            + (5.0 - 2.0 * c1 + 28.0 * t1 - 3.0 * c1.powi(2) + 8.0 * ep2 + 24.0 * t1.powi(2))
+                * d.powi(5)
+                / 120.0)
+            / phi1.cos();
+
+        (
+            self.central_meridian + lambda * RADIAN_TO_DEGREE,
+            phi * RADIAN_TO_DEGREE,
+        )
+    }
+}
+
+// distance along the meridian from the equator to the latitude
+fn meridian_arc(a: f64, e2: f64, phi: f64) -> f64 {
+    let e4 = e2.powi(2);
+    let e6 = e2.powi(3);
+    a * ((1.0 - e2 / 4.0 - 3.0 * e4 / 64.0 - 5.0 * e6 / 256.0) * phi
+        - (3.0 * e2 / 8.0 + 3.0 * e4 / 32.0 + 45.0 * e6 / 1024.0) * (2.0 * phi).sin()
+        + (15.0 * e4 / 256.0 + 45.0 * e6 / 1024.0) * (4.0 * phi).sin()
+        - (35.0 * e6 / 3072.0) * (6.0 * phi).sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reference values from the Krüger series
+    #[test]
+    fn utm_k() {
+        let tm = TransverseMercator::UTM_K;
+        for (longitude, latitude, easting, northing) in [
+            (127.5, 38.0, 1_000_000.0, 2_000_000.0),
+            (126.978, 37.5665, 953_901.165, 1_952_032.081),
+            (124.5, 33.0, 719_700.010, 1_449_470.834),
+            (131.8647, 37.2426, 1_387_217.484, 1_924_906.035),
+        ] {
+            let (x, y) = tm.forward(longitude, latitude).unwrap();
+            assert!((x - easting).abs() < 0.01, "{} {}", x, easting);
+            assert!((y - northing).abs() < 0.01, "{} {}", y, northing);
+
+            let (lon, lat) = tm.inverse(x, y);
+            assert!((lon - longitude).abs() < 1e-7);
+            assert!((lat - latitude).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn central_belt() {
+        let tm = TransverseMercator::CENTRAL_BELT;
+        let (x, y) = tm.forward(126.978, 37.5665).unwrap();
+        assert!((x - 198_056.367).abs() < 0.01);
+        assert!((y - 551_885.031).abs() < 0.01);
+    }
+}
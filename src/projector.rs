@@ -0,0 +1,230 @@
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+use crate::datum::Datum;
+use crate::error::GridError;
+#[cfg(feature = "std")]
+use crate::footprint::{BoundingBox, Coverage};
+use crate::grid_spec::GridSpec;
+use crate::kma_grid::{KmaGrid, OutOfRange};
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::{LambertConformalConic, Projection, TransverseMercator};
+#[cfg(feature = "std")]
+use crate::raster::Polygon;
+
+// Converts points on a grid with the projection constants computed once
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projector {
+    spec: GridSpec,
+    grid: ProjectedGrid<LambertConformalConic>,
+}
+
+impl Projector {
+    pub fn new(spec: GridSpec) -> Projector {
+        Projector {
+            spec,
+            grid: spec.projected_grid(),
+        }
+    }
+
+    // shared projector of GridSpec::DFS_5KM
+    pub fn dfs() -> &'static Projector {
+        #[cfg(feature = "std")]
+        {
+            static DFS: OnceLock<Projector> = OnceLock::new();
+            DFS.get_or_init(|| Projector::new(GridSpec::DFS_5KM))
+        }
+        // OnceLock is in std only
+        #[cfg(not(feature = "std"))]
+        {
+            static DFS: spin::Once<Projector> = spin::Once::new();
+            DFS.call_once(|| Projector::new(GridSpec::DFS_5KM))
+        }
+    }
+
+    pub fn spec(&self) -> &GridSpec {
+        &self.spec
+    }
+
+    pub fn projected_grid(&self) -> &ProjectedGrid<LambertConformalConic> {
+        &self.grid
+    }
+
+    // unrounded grid coordinates of a point, where integers are grid centres
+    pub fn gcs_to_grid(&self, longitude: f64, latitude: f64) -> Result<(f64, f64), GridError> {
+        self.grid.gcs_to_grid(longitude, latitude)
+    }
+
+    // longitude and latitude of unrounded grid coordinates
+    pub fn grid_to_gcs(&self, x: f64, y: f64) -> (f64, f64) {
+        self.grid.grid_to_gcs(x, y)
+    }
+
+    pub fn from_gcs(&self, longitude: f64, latitude: f64) -> Result<KmaGrid, GridError> {
+        self.from_gcs_with(longitude, latitude, OutOfRange::Error)
+    }
+
+    pub fn from_gcs_with(
+        &self,
+        longitude: f64,
+        latitude: f64,
+        out_of_range: OutOfRange,
+    ) -> Result<KmaGrid, GridError> {
+        self.grid.from_gcs_with(longitude, latitude, out_of_range)
+    }
+
+    // converts a point given in another datum, as the grids are laid on WGS84
+    pub fn from_gcs_on(
+        &self,
+        datum: Datum,
+        longitude: f64,
+        latitude: f64,
+        out_of_range: OutOfRange,
+    ) -> Result<KmaGrid, GridError> {
+        let (longitude, latitude) = datum.to_wgs84(longitude, latitude);
+        self.from_gcs_with(longitude, latitude, out_of_range)
+    }
+
+    pub fn to_gcs_on(&self, datum: Datum, grid: KmaGrid) -> Result<(f64, f64), GridError> {
+        let (longitude, latitude) = self.to_gcs(grid)?;
+        Ok(datum.from_wgs84(longitude, latitude))
+    }
+
+    // converts a point in Korea 2000 transverse Mercator metres, treating
+    // GRS80 longitude and latitude as WGS84
+    pub fn from_tm(
+        &self,
+        tm: &TransverseMercator,
+        easting: f64,
+        northing: f64,
+        out_of_range: OutOfRange,
+    ) -> Result<KmaGrid, GridError> {
+        let (longitude, latitude) = tm.inverse(easting, northing);
+        self.from_gcs_with(longitude, latitude, out_of_range)
+    }
+
+    pub fn to_tm(&self, tm: &TransverseMercator, grid: KmaGrid) -> Result<(f64, f64), GridError> {
+        let (longitude, latitude) = self.to_gcs(grid)?;
+        tm.forward(longitude, latitude)
+    }
+
+    pub fn to_gcs(&self, grid: KmaGrid) -> Result<(f64, f64), GridError> {
+        self.grid.to_gcs(grid)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn corners(&self, grid: KmaGrid) -> Result<[(f64, f64); 4], GridError> {
+        self.grid.corners(grid)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn outline(&self, grid: KmaGrid, segments: usize) -> Result<Vec<(f64, f64)>, GridError> {
+        self.grid.outline(grid, segments)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn bounding_box(&self, grid: KmaGrid) -> Result<BoundingBox, GridError> {
+        self.grid.bounding_box(grid)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn range_bounding_box(
+        &self,
+        first: KmaGrid,
+        last: KmaGrid,
+    ) -> Result<BoundingBox, GridError> {
+        self.grid.range_bounding_box(first, last)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn cells_in_box(
+        &self,
+        bbox: &BoundingBox,
+        coverage: Coverage,
+    ) -> Result<impl Iterator<Item = KmaGrid> + '_, GridError> {
+        self.grid.cells_in_box(bbox, coverage)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn polygon_cells(&self, polygon: &Polygon) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        self.grid.polygon_cells(polygon)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn multipolygon_cells(
+        &self,
+        polygons: &[Polygon],
+    ) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        self.grid.multipolygon_cells(polygons)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+
+    #[test]
+    fn same_as_spec() {
+        let projector = Projector::new(GridSpec::LDAPS_1_5KM);
+        let spec = projector.spec();
+        for (longitude, latitude) in [(126.978, 37.5665), (121.834, 32.2569), (131.0, 42.0)] {
+            assert_eq!(
+                projector.gcs_to_grid(longitude, latitude),
+                spec.gcs_to_grid(longitude, latitude)
+            );
+            let (x, y) = projector.gcs_to_grid(longitude, latitude).unwrap();
+            assert_eq!(projector.grid_to_gcs(x, y), spec.grid_to_gcs(x, y));
+        }
+    }
+
+    #[test]
+    fn dfs_is_shared() {
+        assert!(std::ptr::eq(Projector::dfs(), Projector::dfs()));
+        assert_eq!(Projector::dfs(), &Projector::new(GridSpec::DFS_5KM));
+        assert_eq!(Projector::dfs().spec(), &GridSpec::DFS_5KM);
+        assert_eq!(
+            Projector::dfs().from_gcs(126.0, 38.0),
+            Ok(KmaGrid::new(43, 136))
+        );
+    }
+
+    #[test]
+    fn ellipsoidal_round_trip() {
+        for ellipsoid in [Ellipsoid::GRS80, Ellipsoid::WGS84] {
+            let projector = Projector::new(GridSpec {
+                ellipsoid,
+                ..GridSpec::DFS_5KM
+            });
+            assert_eq!(projector.gcs_to_grid(126.0, 38.0), Ok((43.0, 136.0)));
+            for (longitude, latitude) in [(126.978, 37.5665), (126.53, 33.5), (131.8647, 37.2426)] {
+                let (x, y) = projector.gcs_to_grid(longitude, latitude).unwrap();
+                let (lon, lat) = projector.grid_to_gcs(x, y);
+                assert!((lon - longitude).abs() < 1e-9);
+                assert!((lat - latitude).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn sphere_and_grs80_difference() {
+        // the KMA sphere and GRS80 agree at the origin and drift apart with the
+        // distance from it, up to about 2 km over the domain
+        let sphere = Projector::dfs();
+        let grs80 = Projector::new(GridSpec {
+            ellipsoid: Ellipsoid::GRS80,
+            ..GridSpec::DFS_5KM
+        });
+        let mut max_difference: f64 = 0.0;
+        for x in (1..=149).step_by(8) {
+            for y in (1..=253).step_by(12) {
+                let (longitude, latitude) = sphere.grid_to_gcs(x as f64, y as f64);
+                let (gx, gy) = grs80.gcs_to_grid(longitude, latitude).unwrap();
+                let difference = ((gx - x as f64).powi(2) + (gy - y as f64).powi(2)).sqrt();
+                max_difference = max_difference.max(difference);
+            }
+        }
+        assert!(max_difference * 5.0 > 1.5);
+        assert!(max_difference * 5.0 < 2.5);
+    }
+}
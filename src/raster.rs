@@ -0,0 +1,206 @@
+use std::ops::RangeInclusive;
+
+use crate::clip::{cell_range, clip_to_cell, signed_area};
+use crate::error::GridError;
+use crate::kma_grid::KmaGrid;
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::Projection;
+
+// Polygon in longitude and latitude, with edges straight in degrees. Rings may
+// run either way round and may repeat the first point at the end.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon {
+    pub exterior: Vec<(f64, f64)>,
+    pub holes: Vec<Vec<(f64, f64)>>,
+}
+
+impl Polygon {
+    pub fn new(exterior: Vec<(f64, f64)>) -> Polygon {
+        Polygon {
+            exterior,
+            holes: Vec::new(),
+        }
+    }
+
+    pub fn with_hole(mut self, hole: Vec<(f64, f64)>) -> Polygon {
+        self.holes.push(hole);
+        self
+    }
+}
+
+impl<P: Projection> ProjectedGrid<P> {
+    // a ring in grid coordinates, with the edges divided into segments of about
+    // half a cell as they curve on the grid
+    pub(crate) fn ring_on_grid(&self, ring: &[(f64, f64)]) -> Result<Vec<(f64, f64)>, GridError> {
+        self.path_on_grid(ring, true)
+    }
+
+    pub(crate) fn path_on_grid(
+        &self,
+        path: &[(f64, f64)],
+        closed: bool,
+    ) -> Result<Vec<(f64, f64)>, GridError> {
+        let mut points = Vec::with_capacity(path.len());
+        for (index, &(longitude, latitude)) in path.iter().enumerate() {
+            let (x0, y0) = self.gcs_to_grid(longitude, latitude)?;
+            points.push((x0, y0));
+            if !closed && index + 1 == path.len() {
+                break;
+            }
+
+            let (next_longitude, next_latitude) = path[(index + 1) % path.len()];
+            let (x1, y1) = self.gcs_to_grid(next_longitude, next_latitude)?;
+            let segments = (2.0 * (x1 - x0).hypot(y1 - y0)).ceil().max(1.0) as usize;
+            for step in 1..segments {
+                let ratio = step as f64 / segments as f64;
+                points.push(self.gcs_to_grid(
+                    longitude + (next_longitude - longitude) * ratio,
+                    latitude + (next_latitude - latitude) * ratio,
+                )?);
+            }
+        }
+        Ok(points)
+    }
+
+    // cells overlapping the polygon with the covered fraction of each cell on
+    // the grid plane, row by row from the lower left
+    pub fn polygon_cells(&self, polygon: &Polygon) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        self.multipolygon_cells(std::slice::from_ref(polygon))
+    }
+
+    // as polygon_cells for polygons that do not overlap each other
+    pub fn multipolygon_cells(
+        &self,
+        polygons: &[Polygon],
+    ) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        let polygons = self.polygons_on_grid(polygons)?;
+        Ok(polygons
+            .rows
+            .clone()
+            .flat_map(|y| polygons.row(y))
+            .collect())
+    }
+
+    pub(crate) fn polygons_on_grid(&self, polygons: &[Polygon]) -> Result<GridPolygons, GridError> {
+        let mut rings = Vec::new();
+        for polygon in polygons {
+            let holes = polygon.holes.iter().map(|hole| (hole, -1.0));
+            for (ring, sign) in [(&polygon.exterior, 1.0)].into_iter().chain(holes) {
+                if !ring.is_empty() {
+                    rings.push((self.ring_on_grid(ring)?, sign));
+                }
+            }
+        }
+
+        let (mut x0, mut y0, mut x1, mut y1) = (self.nx + 1, self.ny + 1, 0, 0);
+        for (ring, sign) in &rings {
+            if *sign > 0.0 {
+                let range = cell_range(ring);
+                (x0, y0) = (x0.min(range.0.max(1)), y0.min(range.1.max(1)));
+                (x1, y1) = (x1.max(range.2.min(self.nx)), y1.max(range.3.min(self.ny)));
+            }
+        }
+        Ok(GridPolygons {
+            rings,
+            columns: x0..=x1,
+            rows: y0..=y1,
+        })
+    }
+}
+
+// Rings of polygons in grid coordinates, signed -1 for holes, with the cells
+// they may cover
+pub(crate) struct GridPolygons {
+    rings: Vec<(Vec<(f64, f64)>, f64)>,
+    pub(crate) columns: RangeInclusive<i32>,
+    pub(crate) rows: RangeInclusive<i32>,
+}
+
+impl GridPolygons {
+    // cells of a row covered by the polygons, with the covered fraction
+    pub(crate) fn row(&self, y: i32) -> impl Iterator<Item = (KmaGrid, f64)> + '_ {
+        self.columns.clone().filter_map(move |x| {
+            let weight: f64 = self
+                .rings
+                .iter()
+                .map(|(ring, sign)| {
+                    sign * signed_area(&clip_to_cell(ring, x as f64, y as f64)).abs()
+                })
+                .sum();
+            // leaves out cells only touched, or covered by rounding errors
+            (weight > 1e-12).then(|| (KmaGrid::new(x, y), weight.min(1.0)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_spec::GridSpec;
+
+    fn square(longitude: f64, latitude: f64, half: f64) -> Vec<(f64, f64)> {
+        vec![
+            (longitude - half, latitude - half),
+            (longitude + half, latitude - half),
+            (longitude + half, latitude + half),
+            (longitude - half, latitude + half),
+        ]
+    }
+
+    fn total(cells: &[(KmaGrid, f64)]) -> f64 {
+        cells.iter().map(|(_, weight)| weight).sum()
+    }
+
+    #[test]
+    fn weights_add_up_to_the_area() {
+        let grid = GridSpec::DFS_5KM.projected_grid();
+        let exterior = square(127.0, 37.5, 0.2);
+        let hole = square(127.0, 37.5, 0.05);
+        let area = |ring: &[(f64, f64)]| signed_area(&grid.ring_on_grid(ring).unwrap()).abs();
+
+        let cells = grid.polygon_cells(&Polygon::new(exterior.clone())).unwrap();
+        assert!((total(&cells) - area(&exterior)).abs() < 1e-9);
+        assert!(cells
+            .iter()
+            .all(|&(_, weight)| weight > 0.0 && weight <= 1.0));
+
+        // the cell in the middle of the hole is left out
+        let centre = grid.from_gcs(127.0, 37.5).unwrap();
+        let with_hole = Polygon::new(exterior.clone()).with_hole(hole.clone());
+        let cells = grid.polygon_cells(&with_hole).unwrap();
+        assert!((total(&cells) - area(&exterior) + area(&hole)).abs() < 1e-9);
+        assert!(cells.iter().all(|&(cell, _)| cell != centre));
+
+        // clockwise rings and a second polygon
+        let mut reversed = exterior.clone();
+        reversed.reverse();
+        let island = Polygon::new(square(126.5, 33.4, 0.1));
+        let cells = grid
+            .multipolygon_cells(&[Polygon::new(reversed), island.clone()])
+            .unwrap();
+        let island_area = area(&island.exterior);
+        assert!((total(&cells) - area(&exterior) - island_area).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cell_outline() {
+        // the outline of a cell covers the cell alone
+        let grid = GridSpec::DFS_5KM.projected_grid();
+        let seoul = KmaGrid::new(60, 127);
+        let outline = grid.outline(seoul, 16).unwrap();
+        let cells = grid.polygon_cells(&Polygon::new(outline)).unwrap();
+        for (cell, weight) in cells {
+            if cell == seoul {
+                assert!(weight > 0.9999);
+            } else {
+                assert!(weight < 1e-4);
+            }
+        }
+
+        let outside = Polygon::new(square(150.0, 20.0, 1.0));
+        assert_eq!(grid.polygon_cells(&outside), Ok(Vec::new()));
+        assert!(grid
+            .polygon_cells(&Polygon::new(square(127.0, 89.5, 1.0)))
+            .is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::error::GridError;
+use crate::grid_spec::GridSpec;
+use crate::kma_grid::KmaGrid;
+use crate::projected_grid::ProjectedGrid;
+use crate::projection::Projection;
+use crate::projector::Projector;
+
+// Piece of a line inside a single cell, from and to in grid coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Piece {
+    pub(crate) grid: KmaGrid,
+    pub(crate) from: (f64, f64),
+    pub(crate) to: (f64, f64),
+}
+
+impl<P: Projection> ProjectedGrid<P> {
+    // a polyline in longitude and latitude cut at the cell edges, in the order of
+    // the line, leaving out the pieces outside of the grid
+    pub(crate) fn polyline_pieces(&self, line: &[(f64, f64)]) -> Result<Vec<Piece>, GridError> {
+        let points = self.path_on_grid(line, false)?;
+        let mut pieces = Vec::new();
+        for pair in points.windows(2) {
+            traverse(pair[0], pair[1], |piece| {
+                if self.contains(&piece.grid) {
+                    pieces.push(piece);
+                }
+            });
+        }
+        Ok(pieces)
+    }
+}
+
+// walks the cells along a segment by Amanatides and Woo, giving every piece longer than zero
+fn traverse((x0, y0): (f64, f64), (x1, y1): (f64, f64), mut visit: impl FnMut(Piece)) {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    // a segment of no length has no piece longer than zero
+    if dx == 0.0 && dy == 0.0 {
+        return;
+    }
+    let (mut x, mut y) = ((x0 + 0.5).floor() as i32, (y0 + 0.5).floor() as i32);
+    let (step_x, step_y) = (dx.signum() as i32, dy.signum() as i32);
+
+    // t along the segment, 0 ~ 1, to the next cell edge of each axis and between the edges
+    let edge = |cell: i32, step: i32, start: f64, delta: f64| {
+        if delta == 0.0 {
+            f64::INFINITY
+        } else {
+            (cell as f64 + 0.5 * step as f64 - start) / delta
+        }
+    };
+    let mut next_x = edge(x, step_x, x0, dx);
+    let mut next_y = edge(y, step_y, y0, dy);
+    let (delta_x, delta_y) = (1.0 / dx.abs(), 1.0 / dy.abs());
+
+    let at = |t: f64| (x0 + dx * t, y0 + dy * t);
+    let mut t = 0.0;
+    loop {
+        let t_next = next_x.min(next_y).min(1.0);
+        if t_next > t {
+            visit(Piece {
+                grid: KmaGrid::new(x, y),
+                from: at(t),
+                to: at(t_next),
+            });
+        }
+        if t_next >= 1.0 {
+            break;
+        }
+        t = t_next;
+        // through a corner both axes step at once, skipping the cell touched at a point
+        if next_x == t_next {
+            x += step_x;
+            next_x += delta_x;
+        }
+        if next_y == t_next {
+            y += step_y;
+            next_y += delta_y;
+        }
+    }
+}
+
+impl Projector {
+    // cells a polyline in longitude and latitude passes through in order, with
+    // the ground length in km inside each. A cell comes again when the line
+    // returns to it later, and the parts of the line off the grid are left out.
+    pub fn polyline_cells(&self, line: &[(f64, f64)]) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        let grid = self.projected_grid();
+        let mut cells: Vec<(KmaGrid, f64)> = Vec::new();
+        for piece in grid.polyline_pieces(line)? {
+            let (x, y) = (piece.to.0 - piece.from.0, piece.to.1 - piece.from.1);
+            let (_, latitude) = self.grid_to_gcs(
+                (piece.from.0 + piece.to.0) * 0.5,
+                (piece.from.1 + piece.to.1) * 0.5,
+            );
+            // short enough for the scale factor at the middle
+            let length = x.hypot(y) * grid.dx / grid.projection.scale_factor(latitude);
+            match cells.last_mut() {
+                Some((last, total)) if *last == piece.grid => *total += length,
+                _ => cells.push((piece.grid, length)),
+            }
+        }
+        Ok(cells)
+    }
+}
+
+impl GridSpec {
+    pub fn polyline_cells(&self, line: &[(f64, f64)]) -> Result<Vec<(KmaGrid, f64)>, GridError> {
+        Projector::new(*self).polyline_cells(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ellipsoid::Ellipsoid;
+
+    fn assert_connected(cells: &[(KmaGrid, f64)]) {
+        for pair in cells.windows(2) {
+            let (a, b) = (pair[0].0, pair[1].0);
+            assert_ne!(a, b);
+            assert!((a.x() - b.x()).abs() <= 1 && (a.y() - b.y()).abs() <= 1);
+        }
+        assert!(cells.iter().all(|&(_, length)| length > 0.0));
+    }
+
+    #[test]
+    fn segment() {
+        let mut pieces = Vec::new();
+        traverse((1.0, 1.0), (3.0, 2.0), |piece| pieces.push(piece));
+        // crossing x = 1.5, y = 1.5 and x = 2.5 in turn
+        let cells: Vec<_> = pieces
+            .iter()
+            .map(|piece| (piece.grid.x(), piece.grid.y()))
+            .collect();
+        assert_eq!(cells, vec![(1, 1), (2, 1), (2, 2), (3, 2)]);
+        assert_eq!(pieces[0].from, (1.0, 1.0));
+        assert_eq!(pieces[3].to, (3.0, 2.0));
+
+        // straight through the corner of four cells
+        let mut pieces = Vec::new();
+        traverse((1.0, 1.0), (2.0, 2.0), |piece| pieces.push(piece));
+        let cells: Vec<_> = pieces
+            .iter()
+            .map(|piece| (piece.grid.x(), piece.grid.y()))
+            .collect();
+        assert_eq!(cells, vec![(1, 1), (2, 2)]);
+
+        let mut pieces = Vec::new();
+        traverse((1.2, 1.2), (1.2, 1.2), |piece| pieces.push(piece));
+        assert!(pieces.is_empty());
+    }
+
+    #[test]
+    fn route() {
+        let dfs = Projector::dfs();
+        // Seoul to Busan
+        let line = [
+            (126.978, 37.5665),
+            (127.5, 36.8),
+            (128.0, 36.0),
+            (129.075, 35.18),
+        ];
+        let cells = dfs.polyline_cells(&line).unwrap();
+        assert_connected(&cells);
+        assert_eq!(cells[0].0, KmaGrid::from_gcs(126.978, 37.5665));
+        assert_eq!(cells.last().unwrap().0, KmaGrid::from_gcs(129.075, 35.18));
+
+        let total: f64 = cells.iter().map(|(_, length)| length).sum();
+        let sphere = Ellipsoid::KMA_SPHERE;
+        let distance: f64 = line
+            .windows(2)
+            .map(|pair| {
+                sphere
+                    .distance(pair[0].0, pair[0].1, pair[1].0, pair[1].1)
+                    .unwrap()
+            })
+            .sum();
+        assert!((total / distance - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn off_the_grid() {
+        // from the Yellow Sea beyond the west edge into the grid
+        let cells = Projector::dfs()
+            .polyline_cells(&[(120.0, 37.0), (126.0, 37.0)])
+            .unwrap();
+        assert_connected(&cells);
+        assert_eq!(cells[0].0.x(), 1);
+        assert_eq!(
+            Projector::dfs().polyline_cells(&[(150.0, 20.0), (151.0, 21.0)]),
+            Ok(Vec::new())
+        );
+        assert_eq!(Projector::dfs().polyline_cells(&[]), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn repeated_points() {
+        let dfs = Projector::dfs();
+        assert_eq!(
+            dfs.polyline_cells(&[(127.0, 37.5), (127.0, 37.5)]),
+            Ok(Vec::new())
+        );
+        // a point given twice on the way adds nothing
+        let line = [(126.978, 37.5665), (127.5, 36.8), (128.0, 36.0)];
+        let repeated = [line[0], line[1], line[1], line[2]];
+        assert_eq!(dfs.polyline_cells(&repeated), dfs.polyline_cells(&line));
+    }
+}
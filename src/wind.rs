@@ -0,0 +1,151 @@
+use crate::error::GridError;
+use crate::grid_spec::GridSpec;
+use crate::kma_grid::KmaGrid;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::projector::Projector;
+
+// Grid relative components are along the +x and +y axes of the grid, earth
+// relative ones along true east and north. Fields hold nx * ny values with x
+// varying fastest from grid (1, 1), as in the model output.
+
+// rotates (u, v) clockwise by the angle in degree
+fn rotate(angle: f64, u: f64, v: f64) -> (f64, f64) {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    (u * cos + v * sin, v * cos - u * sin)
+}
+
+impl Projector {
+    pub fn wind_to_earth(
+        &self,
+        longitude: f64,
+        latitude: f64,
+        u: f64,
+        v: f64,
+    ) -> Result<(f64, f64), GridError> {
+        let angle = self.convergence_angle(longitude, latitude)?;
+        Ok(rotate(angle, u, v))
+    }
+
+    pub fn wind_to_grid(
+        &self,
+        longitude: f64,
+        latitude: f64,
+        u: f64,
+        v: f64,
+    ) -> Result<(f64, f64), GridError> {
+        let angle = self.convergence_angle(longitude, latitude)?;
+        Ok(rotate(-angle, u, v))
+    }
+
+    pub fn cell_wind_to_earth(
+        &self,
+        grid: KmaGrid,
+        u: f64,
+        v: f64,
+    ) -> Result<(f64, f64), GridError> {
+        let angle = self.cell_convergence_angle(grid)?;
+        Ok(rotate(angle, u, v))
+    }
+
+    pub fn cell_wind_to_grid(
+        &self,
+        grid: KmaGrid,
+        u: f64,
+        v: f64,
+    ) -> Result<(f64, f64), GridError> {
+        let angle = self.cell_convergence_angle(grid)?;
+        Ok(rotate(-angle, u, v))
+    }
+
+    // rotates grid relative u and v fields in place to earth relative ones
+    pub fn field_to_earth(&self, u: &mut [f64], v: &mut [f64]) {
+        self.rotate_field(1.0, u, v)
+    }
+
+    // rotates earth relative u and v fields in place to grid relative ones
+    pub fn field_to_grid(&self, u: &mut [f64], v: &mut [f64]) {
+        self.rotate_field(-1.0, u, v)
+    }
+
+    fn rotate_field(&self, sign: f64, u: &mut [f64], v: &mut [f64]) {
+        let GridSpec { nx, ny, .. } = *self.spec();
+        let size = nx as usize * ny as usize;
+        assert!(
+            u.len() == size && v.len() == size,
+            "u and v of {} and {} values for a {} x {} grid",
+            u.len(),
+            v.len(),
+            nx,
+            ny
+        );
+
+        let lcc = &self.projected_grid().projection;
+        for (index, (u, v)) in u.iter_mut().zip(v.iter_mut()).enumerate() {
+            let x = (index % nx as usize) as f64 + 1.0;
+            let y = (index / nx as usize) as f64 + 1.0;
+            let (longitude, _) = self.grid_to_gcs(x, y);
+            (*u, *v) = rotate(sign * lcc.convergence(longitude), *u, *v);
+        }
+    }
+}
+
+impl GridSpec {
+    pub fn field_to_earth(&self, u: &mut [f64], v: &mut [f64]) {
+        Projector::new(*self).field_to_earth(u, v)
+    }
+
+    pub fn field_to_grid(&self, u: &mut [f64], v: &mut [f64]) {
+        Projector::new(*self).field_to_grid(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_vector() {
+        let dfs = Projector::dfs();
+        assert_eq!(dfs.wind_to_earth(126.0, 37.0, 3.0, 4.0), Ok((3.0, 4.0)));
+
+        // grid north leans east at Dokdo, so a wind along +y blows to the north east
+        let dokdo = KmaGrid::new(144, 123);
+        let (u, v) = dfs.cell_wind_to_earth(dokdo, 0.0, 10.0).unwrap();
+        assert!(u > 0.0 && v > 0.0);
+        assert!((u.hypot(v) - 10.0).abs() < 1e-12);
+        let angle = dfs.cell_convergence_angle(dokdo).unwrap();
+        assert!((u.atan2(v).to_degrees() - angle).abs() < 1e-12);
+
+        let (gu, gv) = dfs.cell_wind_to_grid(dokdo, u, v).unwrap();
+        assert!(gu.abs() < 1e-12 && (gv - 10.0).abs() < 1e-12);
+        assert!(dfs
+            .cell_wind_to_earth(KmaGrid::new(0, 0), 1.0, 1.0)
+            .is_err());
+    }
+
+    #[test]
+    fn field() {
+        let spec = GridSpec::DFS_5KM;
+        let size = (spec.nx * spec.ny) as usize;
+        let (mut u, mut v) = (vec![5.0; size], vec![-2.0; size]);
+        spec.field_to_earth(&mut u, &mut v);
+
+        let dokdo = KmaGrid::new(144, 123);
+        let index = ((dokdo.y() - 1) * spec.nx + dokdo.x() - 1) as usize;
+        assert_eq!(
+            Projector::dfs().cell_wind_to_earth(dokdo, 5.0, -2.0),
+            Ok((u[index], v[index]))
+        );
+
+        spec.field_to_grid(&mut u, &mut v);
+        assert!(u.iter().all(|u| (u - 5.0).abs() < 1e-12));
+        assert!(v.iter().all(|v| (v + 2.0).abs() < 1e-12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn field_size() {
+        Projector::dfs().field_to_earth(&mut [0.0; 3], &mut [0.0; 3]);
+    }
+}
@@ -0,0 +1,126 @@
+use kma_grid::{Ellipsoid, GridSpec, KmaGrid, OutOfRange};
+
+// Conformance of the DFS 5 km grid with what KMA publishes: the cells of places
+// in the region-to-grid table of the short-range forecast service, every row of
+// tests/data/region_grid.csv as extracted by tests/data/region_grid.py, and the
+// output of KMA's C converter (lamcproj and map_conv) compiled from
+// tests/data/lamcproj.c, which also tells how to make tests/data/lamcproj.txt.
+// The other presets are held to the points KMA publishes of their grids.
+
+const REGION_TABLE: &str = include_str!("data/region_grid.csv");
+const CONVERTER: &str = include_str!("data/lamcproj.txt");
+const PRESET_POINTS: &str = include_str!("data/preset_points.csv");
+
+fn lines(table: &str) -> impl Iterator<Item = &str> {
+    table
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+#[test]
+fn region_table() {
+    let mut places = 0;
+    for line in lines(REGION_TABLE).skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [place, longitude, latitude, x, y] = fields[..] else {
+            panic!("malformed row {}", line);
+        };
+        let grid = KmaGrid::new(x.parse().unwrap(), y.parse().unwrap());
+        assert_eq!(
+            KmaGrid::try_from_gcs(longitude.parse().unwrap(), latitude.parse().unwrap()),
+            Ok(grid),
+            "{}",
+            place
+        );
+        places += 1;
+    }
+    assert!(places > 0);
+}
+
+#[test]
+fn preset_points() {
+    let mut points = 0;
+    for line in lines(PRESET_POINTS).skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [grid, point, longitude, latitude, x, y, _source] = fields[..] else {
+            panic!("malformed row {}", line);
+        };
+        let spec = match grid {
+            "DFS_5KM" => GridSpec::DFS_5KM,
+            "LDAPS_1_5KM" => GridSpec::LDAPS_1_5KM,
+            _ => panic!("unknown grid {}", grid),
+        };
+        let (longitude, latitude) = (longitude.parse().unwrap(), latitude.parse().unwrap());
+        assert_eq!(
+            KmaGrid::try_from_gcs_in(&spec, longitude, latitude, OutOfRange::Error),
+            Ok(KmaGrid::new(x.parse().unwrap(), y.parse().unwrap())),
+            "{} {}",
+            grid,
+            point
+        );
+        points += 1;
+    }
+    assert!(points > 0);
+}
+
+// The converter keeps its parameters in floats and divides Re by the grid length
+// in float, which makes its earth 6371.00891 km in radius instead of 6371.00877.
+// Cell centres move by up to 2 cm, enough to round a float the other way.
+fn converter_spec() -> GridSpec {
+    GridSpec {
+        ellipsoid: Ellipsoid {
+            semi_major_axis: f64::from(Ellipsoid::KMA_SPHERE.semi_major_axis as f32 / 5.0) * 5.0,
+            flattening: 0.0,
+        },
+        ..GridSpec::DFS_5KM
+    }
+}
+
+#[test]
+fn converter_table() {
+    // the converter takes and gives floats, so its points are read as f32,
+    // which widen to the very values it used, and its longitudes and latitudes
+    // are compared as f32
+    let spec = converter_spec();
+    let (mut forward, mut inverse) = (0, 0);
+    for line in lines(CONVERTER) {
+        let fields: Vec<&str> = line.split(' ').collect();
+        match fields[..] {
+            ["gcs", longitude, latitude, x, y] => {
+                let longitude = f64::from(longitude.parse::<f32>().unwrap());
+                let latitude = f64::from(latitude.parse::<f32>().unwrap());
+                let grid = KmaGrid::new(x.parse().unwrap(), y.parse().unwrap());
+                assert_eq!(
+                    KmaGrid::try_from_gcs(longitude, latitude),
+                    Ok(grid),
+                    "{}",
+                    line
+                );
+                assert_eq!(
+                    KmaGrid::try_from_gcs_in(&spec, longitude, latitude, OutOfRange::Error),
+                    Ok(grid),
+                    "{}",
+                    line
+                );
+                forward += 1;
+            }
+            ["grid", x, y, longitude, latitude] => {
+                let grid = KmaGrid::new(x.parse().unwrap(), y.parse().unwrap());
+                let expected: (f32, f32) = (longitude.parse().unwrap(), latitude.parse().unwrap());
+                let (lon, lat) = grid.to_gcs_in(&spec).unwrap();
+                assert_eq!((lon as f32, lat as f32), expected, "{}", line);
+
+                // the grid of the crate is at most a float step off
+                let (lon, lat) = grid.to_gcs().unwrap();
+                let steps = |value: f64, expected: f32| {
+                    ((value as f32).to_bits() as i64 - expected.to_bits() as i64).abs()
+                };
+                assert!(steps(lon, expected.0) <= 1, "{}", line);
+                assert!(steps(lat, expected.1) <= 1, "{}", line);
+                inverse += 1;
+            }
+            _ => panic!("malformed line {}", line),
+        }
+    }
+    assert!(forward > 3000 && inverse > 2000);
+}
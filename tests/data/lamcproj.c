@@ -0,0 +1,159 @@
+/*
+ * lamcproj and map_conv, the Lambert conformal conic converter of the sample
+ * code in KMA's short-range forecast API guide, with the map parameters of the
+ * 5 km grid, and a main printing the table in lamcproj.txt:
+ *
+ *   cc -O0 -o lamcproj lamcproj.c -lm && ./lamcproj > lamcproj.txt
+ *
+ * Only main is new; the converter keeps its single precision arguments.
+ */
+#include <stdio.h>
+#include <math.h>
+
+#define NX 149
+#define NY 253
+
+struct lamc_parameter {
+  float Re;    /* radius of the earth [km] */
+  float grid;  /* grid length [km] */
+  float slat1; /* standard latitude [degree] */
+  float slat2; /* standard latitude [degree] */
+  float olon;  /* longitude of the reference point [degree] */
+  float olat;  /* latitude of the reference point [degree] */
+  float xo;    /* x of the reference point [grid] */
+  float yo;    /* y of the reference point [grid] */
+  int first;   /* 0 until the constants are computed */
+};
+
+int lamcproj(float *lon, float *lat, float *x, float *y, int code, struct lamc_parameter *map)
+{
+  static double PI, DEGRAD, RADDEG;
+  static double re, olon, olat, sn, sf, ro;
+  double slat1, slat2, alon, alat, xn, yn, ra, theta;
+
+  if ((*map).first == 0) {
+    PI = asin(1.0) * 2.0;
+    DEGRAD = PI / 180.0;
+    RADDEG = 180.0 / PI;
+
+    re = (*map).Re / (*map).grid;
+    slat1 = (*map).slat1 * DEGRAD;
+    slat2 = (*map).slat2 * DEGRAD;
+    olon = (*map).olon * DEGRAD;
+    olat = (*map).olat * DEGRAD;
+
+    sn = tan(PI * 0.25 + slat2 * 0.5) / tan(PI * 0.25 + slat1 * 0.5);
+    sn = log(cos(slat1) / cos(slat2)) / log(sn);
+    sf = tan(PI * 0.25 + slat1 * 0.5);
+    sf = pow(sf, sn) * cos(slat1) / sn;
+    ro = tan(PI * 0.25 + olat * 0.5);
+    ro = re * sf / pow(ro, sn);
+    (*map).first = 1;
+  }
+
+  if (code == 0) {
+    ra = tan(PI * 0.25 + (*lat) * DEGRAD * 0.5);
+    ra = re * sf / pow(ra, sn);
+    theta = (*lon) * DEGRAD - olon;
+    if (theta > PI) theta -= 2.0 * PI;
+    if (theta < -PI) theta += 2.0 * PI;
+    theta *= sn;
+    *x = (float)(ra * sin(theta)) + (*map).xo;
+    *y = (float)(ro - ra * cos(theta)) + (*map).yo;
+  } else {
+    xn = *x - (*map).xo;
+    yn = ro - *y + (*map).yo;
+    ra = sqrt(xn * xn + yn * yn);
+    if (sn < 0.0) ra = -ra;
+    alat = pow((re * sf / ra), (1.0 / sn));
+    alat = 2.0 * atan(alat) - PI * 0.5;
+    if (fabs(xn) <= 0.0) {
+      theta = 0.0;
+    } else {
+      if (fabs(yn) <= 0.0) {
+        theta = PI * 0.5;
+        if (xn < 0.0) theta = -theta;
+      } else
+        theta = atan2(xn, yn);
+    }
+    alon = theta / sn + olon;
+    *lat = (float)(alat * RADDEG);
+    *lon = (float)(alon * RADDEG);
+  }
+  return 0;
+}
+
+int map_conv(float *lon, float *lat, float *x, float *y, int code, struct lamc_parameter map)
+{
+  float lon1, lat1, x1, y1;
+
+  if (code == 0) {
+    lon1 = *lon;
+    lat1 = *lat;
+    lamcproj(&lon1, &lat1, &x1, &y1, 0, &map);
+    *x = (int)(x1 + 1.5);
+    *y = (int)(y1 + 1.5);
+  }
+  if (code == 1) {
+    x1 = *x - 1;
+    y1 = *y - 1;
+    lamcproj(&lon1, &lat1, &x1, &y1, 1, &map);
+    *lon = lon1;
+    *lat = lat1;
+  }
+  return 0;
+}
+
+int main(void)
+{
+  struct lamc_parameter map = {6371.00877, 5.0, 30.0, 60.0, 126.0, 38.0, 210 / 5.0, 675 / 5.0, 0};
+  float lon, lat, x, y;
+  unsigned int seed = 12345;
+  int i, j;
+
+  printf("# generated by lamcproj.c\n");
+  printf("# gcs lon lat x y: map_conv code 0, cells within 1 ~ %d, 1 ~ %d only\n", NX, NY);
+  printf("# grid x y lon lat: map_conv code 1\n");
+
+  /* every 0.25 degree over the domain and around it */
+  for (i = 0; i <= 48; i++) {
+    for (j = 0; j <= 52; j++) {
+      lon = 121.0f + 0.25f * i;
+      lat = 31.0f + 0.25f * j;
+      map_conv(&lon, &lat, &x, &y, 0, map);
+      if (x >= 1 && x <= NX && y >= 1 && y <= NY)
+        printf("gcs %.9g %.9g %d %d\n", lon, lat, (int)x, (int)y);
+    }
+  }
+
+  /* points off the lattice, from a linear congruential generator */
+  for (i = 0; i < 2000; i++) {
+    seed = seed * 1103515245u + 12345u;
+    lon = 122.0f + 11.0f * (float)((seed >> 8) & 0xffff) / 65536.0f;
+    seed = seed * 1103515245u + 12345u;
+    lat = 31.5f + 12.0f * (float)((seed >> 8) & 0xffff) / 65536.0f;
+    map_conv(&lon, &lat, &x, &y, 0, map);
+    if (x >= 1 && x <= NX && y >= 1 && y <= NY)
+      printf("gcs %.9g %.9g %d %d\n", lon, lat, (int)x, (int)y);
+  }
+
+  /* every fourth cell, and the corners and the reference point */
+  for (i = 1; i <= NX; i += 4) {
+    for (j = 1; j <= NY; j += 4) {
+      x = i;
+      y = j;
+      map_conv(&lon, &lat, &x, &y, 1, map);
+      printf("grid %d %d %.9g %.9g\n", i, j, lon, lat);
+    }
+  }
+  {
+    static const int cells[][2] = {{NX, 1}, {1, NY}, {NX, NY}, {43, 136}, {60, 127}, {144, 123}};
+    for (i = 0; i < 6; i++) {
+      x = cells[i][0];
+      y = cells[i][1];
+      map_conv(&lon, &lat, &x, &y, 1, map);
+      printf("grid %d %d %.9g %.9g\n", cells[i][0], cells[i][1], lon, lat);
+    }
+  }
+  return 0;
+}
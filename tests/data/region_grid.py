@@ -0,0 +1,91 @@
+"""
+Extracts region_grid.csv from the region-to-grid spreadsheet of KMA's
+short-range forecast API guide, the .xlsx with the administrative areas and
+their grid X and Y that comes with the guide on the public data portal:
+
+  python3 region_grid.py <spreadsheet.xlsx> > region_grid.csv
+
+Only the standard library is used. Columns are found by their headers, so the
+spreadsheet is read as KMA ships it; decimal degrees are taken from the
+second/100 columns, or else made up from degrees, minutes and seconds.
+"""
+import sys
+import zipfile
+import xml.etree.ElementTree as ElementTree
+
+NS = {"s": "http://schemas.openxmlformats.org/spreadsheetml/2006/main"}
+PLACE = ["1단계", "2단계", "3단계"]
+
+
+def column_index(reference):
+    index = 0
+    for c in reference:
+        if not c.isalpha():
+            break
+        index = index * 26 + ord(c.upper()) - ord("A") + 1
+    return index - 1
+
+
+def rows(path):
+    with zipfile.ZipFile(path) as book:
+        strings = []
+        if "xl/sharedStrings.xml" in book.namelist():
+            root = ElementTree.fromstring(book.read("xl/sharedStrings.xml"))
+            for item in root.findall("s:si", NS):
+                strings.append("".join(t.text or "" for t in item.iter("{%s}t" % NS["s"])))
+        sheet = ElementTree.fromstring(book.read("xl/worksheets/sheet1.xml"))
+        for row in sheet.iter("{%s}row" % NS["s"]):
+            values = {}
+            for cell in row.findall("s:c", NS):
+                kind = cell.get("t")
+                if kind == "inlineStr":
+                    value = "".join(t.text or "" for t in cell.iter("{%s}t" % NS["s"]))
+                else:
+                    v = cell.find("s:v", NS)
+                    value = "" if v is None else v.text or ""
+                    if kind == "s" and value:
+                        value = strings[int(value)]
+                values[column_index(cell.get("r"))] = value.strip()
+            if values:
+                yield [values.get(i, "") for i in range(max(values) + 1)]
+
+
+def degrees(row, column, prefix):
+    if prefix + "(초/100)" in column:
+        return row[column[prefix + "(초/100)"]]
+    degree, minute, second = (float(row[column[prefix + unit]]) for unit in ["(시)", "(분)", "(초)"])
+    return repr(degree + minute / 60 + second / 3600)
+
+
+def main(path):
+    lines = rows(path)
+    header = [name.replace(" ", "") for name in next(lines)]
+    column = {name: i for i, name in enumerate(header)}
+    missing = [name for name in PLACE + ["격자X", "격자Y"] if name not in column]
+    if missing:
+        sys.exit("no column %s among %s" % (missing, header))
+
+    print("# Extracted from KMA's region-to-grid spreadsheet by region_grid.py")
+    print("place,longitude,latitude,x,y")
+    for row in lines:
+        row += [""] * (len(header) - len(row))
+        place = " ".join(row[column[name]] for name in PLACE if row[column[name]])
+        if not place or not row[column["격자X"]]:
+            continue
+        print(
+            ",".join(
+                [
+                    place.replace(",", " "),
+                    degrees(row, column, "경도"),
+                    degrees(row, column, "위도"),
+                    str(int(float(row[column["격자X"]]))),
+                    str(int(float(row[column["격자Y"]]))),
+                ]
+            )
+        )
+
+
+if __name__ == "__main__":
+    if len(sys.argv) != 2:
+        sys.exit(__doc__)
+    main(sys.argv[1])
@@ -0,0 +1,96 @@
+use kma_grid::{GridSpec, KmaGrid, OutOfRange, Projector};
+
+// The crate against the math of std. Built with --no-default-features
+// --features libm the crate takes its math from libm, which may round the last
+// bit of tan, pow or atan2 differently from the platform: cells are still the
+// same, and continuous coordinates agree to within TOLERANCE. With std they are
+// the same bits.
+
+const TOLERANCE: f64 = 1e-12;
+
+// LambertConformalConic of GridSpec::DFS_5KM in the same order of operations
+struct Reference {
+    n: f64,
+    f: f64,
+    rho_zero: f64,
+}
+
+const EARTH_RADIUS: f64 = 6371.00877;
+const DEGREE_TO_RADIAN: f64 = std::f64::consts::PI / 180.0;
+const RADIAN_TO_DEGREE: f64 = 180.0 / std::f64::consts::PI;
+
+fn q(latitude: f64) -> f64 {
+    (std::f64::consts::PI * 0.25 + 0.5 * latitude).tan()
+}
+
+impl Reference {
+    fn new() -> Reference {
+        let (standard_parallel1, standard_parallel2) =
+            (30.0 * DEGREE_TO_RADIAN, 60.0 * DEGREE_TO_RADIAN);
+        let n = (standard_parallel1.cos() / standard_parallel2.cos()).ln()
+            / (q(standard_parallel2) / q(standard_parallel1)).ln();
+        let f = q(standard_parallel1).powf(n) * standard_parallel1.cos() / n;
+        let rho_zero = EARTH_RADIUS * f / q(38.0 * DEGREE_TO_RADIAN).powf(n);
+        Reference { n, f, rho_zero }
+    }
+
+    fn gcs_to_grid(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        let rho = EARTH_RADIUS * self.f / q(latitude * DEGREE_TO_RADIAN).powf(self.n);
+        let theta = longitude * DEGREE_TO_RADIAN - 126.0 * DEGREE_TO_RADIAN;
+        let x = rho * (theta * self.n).sin();
+        let y = self.rho_zero - rho * (theta * self.n).cos();
+        (x / 5.0 + 43.0, y / 5.0 + 136.0)
+    }
+
+    fn grid_to_gcs(&self, x: f64, y: f64) -> (f64, f64) {
+        let (xn, yn) = ((x - 43.0) * 5.0, self.rho_zero - (y - 136.0) * 5.0);
+        let ra = (xn.powi(2) + yn.powi(2)).sqrt();
+        let latitude = 2.0 * (EARTH_RADIUS * self.f / ra).powf(1.0 / self.n).atan()
+            - std::f64::consts::PI * 0.5;
+        let theta = if xn.abs() == 0.0 { 0.0 } else { xn.atan2(yn) };
+        let longitude = theta / self.n + 126.0 * DEGREE_TO_RADIAN;
+        (longitude * RADIAN_TO_DEGREE, latitude * RADIAN_TO_DEGREE)
+    }
+}
+
+fn assert_close((a, b): (f64, f64), (c, d): (f64, f64)) {
+    if cfg!(feature = "std") {
+        assert_eq!((a.to_bits(), b.to_bits()), (c.to_bits(), d.to_bits()));
+    } else {
+        assert!((a - c).abs() < TOLERANCE && (b - d).abs() < TOLERANCE);
+    }
+}
+
+#[test]
+fn shared_projector() {
+    // the constants of the shared projector are those computed on this build
+    assert_eq!(Projector::dfs(), &Projector::new(GridSpec::DFS_5KM));
+}
+
+#[test]
+fn same_cells() {
+    let reference = Reference::new();
+    let dfs = Projector::dfs();
+    for i in 0..=600 {
+        for j in 0..=650 {
+            let (longitude, latitude) = (121.0 + 0.02 * i as f64, 31.0 + 0.02 * j as f64);
+            let (x, y) = reference.gcs_to_grid(longitude, latitude);
+            assert_close(dfs.gcs_to_grid(longitude, latitude).unwrap(), (x, y));
+            let grid = dfs
+                .from_gcs_with(longitude, latitude, OutOfRange::Unbounded)
+                .unwrap();
+            assert_eq!(grid, KmaGrid::new(x.round() as i32, y.round() as i32));
+        }
+    }
+}
+
+#[test]
+fn same_coordinates() {
+    let reference = Reference::new();
+    for x in 1..=149 {
+        for y in 1..=253 {
+            let centre = KmaGrid::new(x, y).to_gcs().unwrap();
+            assert_close(centre, reference.grid_to_gcs(x as f64, y as f64));
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use kma_grid::{GridSpec, KmaGrid, OutOfRange, Projector};
+use proptest::prelude::*;
+
+// Properties of the DFS 5 km grid over random points in its domain
+
+const SPEC: GridSpec = GridSpec::DFS_5KM;
+
+// points whose cell is in the domain
+fn in_domain() -> impl Strategy<Value = (f64, f64)> {
+    (0.5..SPEC.nx as f64 + 0.5, 0.5..SPEC.ny as f64 + 0.5)
+        .prop_map(|(x, y)| SPEC.grid_to_gcs(x, y))
+        .prop_filter("on a cell edge", |&(longitude, latitude)| {
+            KmaGrid::try_from_gcs(longitude, latitude).is_ok()
+        })
+}
+
+proptest! {
+    #[test]
+    fn centre_within_half_a_diagonal((longitude, latitude) in in_domain()) {
+        let grid = KmaGrid::from_gcs(longitude, latitude);
+        let (centre_longitude, centre_latitude) = grid.to_gcs().unwrap();
+        let (x, y) = SPEC.gcs_to_grid(longitude, latitude).unwrap();
+        let (centre_x, centre_y) = SPEC.gcs_to_grid(centre_longitude, centre_latitude).unwrap();
+        prop_assert!((x - centre_x).abs() <= 0.5 + 1e-9);
+        prop_assert!((y - centre_y).abs() <= 0.5 + 1e-9);
+
+        // on the ground, with the scale factor of the cell
+        let diagonal = SPEC.grid_length * 2f64.sqrt() / SPEC.cell_scale_factor(grid).unwrap();
+        let distance = grid.distance_to_gcs(longitude, latitude).unwrap();
+        prop_assert!(distance <= 0.5 * diagonal * 1.001, "{} km", distance);
+    }
+
+    #[test]
+    fn continuous_round_trip((longitude, latitude) in in_domain()) {
+        let (x, y) = SPEC.gcs_to_grid(longitude, latitude).unwrap();
+        let (back_longitude, back_latitude) = SPEC.grid_to_gcs(x, y);
+        prop_assert!((back_longitude - longitude).abs() < 1e-10);
+        prop_assert!((back_latitude - latitude).abs() < 1e-10);
+        let (back_x, back_y) = SPEC.gcs_to_grid(back_longitude, back_latitude).unwrap();
+        prop_assert!((back_x - x).abs() < 1e-9 && (back_y - y).abs() < 1e-9);
+    }
+
+    #[test]
+    fn x_grows_with_longitude(
+        (longitude, latitude) in in_domain(),
+        step in 1e-6..0.5f64,
+    ) {
+        let (x, _) = SPEC.gcs_to_grid(longitude, latitude).unwrap();
+        let (east, _) = SPEC.gcs_to_grid(longitude + step, latitude).unwrap();
+        prop_assert!(east > x);
+        let cell = KmaGrid::try_from_gcs_with(longitude + step, latitude, OutOfRange::Unbounded).unwrap();
+        prop_assert!(cell.x() >= KmaGrid::from_gcs(longitude, latitude).x());
+    }
+
+    #[test]
+    fn y_grows_with_latitude(
+        (longitude, latitude) in in_domain(),
+        step in 1e-6..0.5f64,
+    ) {
+        let (_, y) = SPEC.gcs_to_grid(longitude, latitude).unwrap();
+        let (_, north) = SPEC.gcs_to_grid(longitude, latitude + step).unwrap();
+        prop_assert!(north > y);
+        let cell = KmaGrid::try_from_gcs_with(longitude, latitude + step, OutOfRange::Unbounded).unwrap();
+        prop_assert!(cell.y() >= KmaGrid::from_gcs(longitude, latitude).y());
+    }
+
+    #[test]
+    fn deterministic((longitude, latitude) in in_domain()) {
+        // the same bits every time, through each entry point
+        let projector = Projector::new(SPEC);
+        let (x, y) = SPEC.gcs_to_grid(longitude, latitude).unwrap();
+        for _ in 0..3 {
+            let (again_x, again_y) = projector.gcs_to_grid(longitude, latitude).unwrap();
+            prop_assert_eq!((again_x.to_bits(), again_y.to_bits()), (x.to_bits(), y.to_bits()));
+        }
+        let grid = KmaGrid::from_gcs(longitude, latitude);
+        prop_assert_eq!(projector.from_gcs(longitude, latitude), Ok(grid));
+        let (centre_longitude, centre_latitude) = grid.to_gcs().unwrap();
+        let again = projector.to_gcs(grid).unwrap();
+        prop_assert_eq!(
+            (again.0.to_bits(), again.1.to_bits()),
+            (centre_longitude.to_bits(), centre_latitude.to_bits())
+        );
+    }
+}